@@ -1,4 +1,9 @@
-use crate::{error::Result, Deserializer, Serializer};
+use crate::{
+    de::{Ignored, OwnedInput, StreamDeserializer},
+    error::{Error, Result},
+    Deserializer, Serializer,
+};
+use base64::prelude::{Engine, BASE64_STANDARD};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -10,6 +15,214 @@ use xml::{
 
 pub const TEXT: &str = "#text";
 pub const CONTENT: &str = "#content";
+/// The name of the field that held text and content in serde-xml-rs 0.6 and 0.7
+pub(crate) const LEGACY_VALUE: &str = "$value";
+pub(crate) const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+/// The name of the elements that hold the items of a sequence in the document root
+pub(crate) const ROOT_ITEM: &str = "item";
+
+/// How byte arrays, such as `serde_bytes::ByteBuf`, are written in element text and attribute
+/// values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BinaryEncoding {
+    /// The bytes are written as they are, so they must be valid UTF-8
+    #[default]
+    Utf8,
+    /// `xs:base64Binary`, with the standard alphabet and padding
+    Base64,
+    /// `xs:hexBinary`, written in upper case and read in any case
+    Hex,
+}
+
+impl BinaryEncoding {
+    pub(crate) fn encode(self, bytes: &[u8]) -> Result<String> {
+        Ok(match self {
+            BinaryEncoding::Utf8 => String::from_utf8(bytes.to_vec())?,
+            BinaryEncoding::Base64 => BASE64_STANDARD.encode(bytes),
+            BinaryEncoding::Hex => bytes.iter().map(|byte| format!("{byte:02X}")).collect(),
+        })
+    }
+
+    pub(crate) fn decode(self, text: &str) -> Result<Vec<u8>> {
+        match self {
+            BinaryEncoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            BinaryEncoding::Base64 => {
+                let text: String = text.split_ascii_whitespace().collect();
+                BASE64_STANDARD
+                    .decode(&text)
+                    .map_err(|_| Error::Unexpected {
+                        expected: "base64 binary",
+                        but_got: text,
+                    })
+            }
+            BinaryEncoding::Hex => {
+                let text: String = text.split_ascii_whitespace().collect();
+                let invalid = || Error::Unexpected {
+                    expected: "hex binary",
+                    but_got: text.clone(),
+                };
+                if !text.len().is_multiple_of(2) || !text.is_ascii() {
+                    return Err(invalid());
+                }
+                (0..text.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|_| invalid()))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The lexical forms of booleans that are accepted when deserializing, and written when
+/// serializing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Booleans {
+    pub(crate) true_values: Vec<String>,
+    pub(crate) false_values: Vec<String>,
+    pub(crate) case_insensitive: bool,
+    pub(crate) true_output: String,
+    pub(crate) false_output: String,
+}
+
+impl Default for Booleans {
+    fn default() -> Self {
+        Self {
+            true_values: vec!["true".to_string(), "1".to_string()],
+            false_values: vec!["false".to_string(), "0".to_string()],
+            case_insensitive: false,
+            true_output: "true".to_string(),
+            false_output: "false".to_string(),
+        }
+    }
+}
+
+impl Booleans {
+    pub(crate) fn parse(&self, text: &str) -> Result<bool> {
+        let text = text.trim();
+        let matches = |value: &String| {
+            if self.case_insensitive {
+                value.eq_ignore_ascii_case(text)
+            } else {
+                value == text
+            }
+        };
+        if self.true_values.iter().any(matches) {
+            Ok(true)
+        } else if self.false_values.iter().any(matches) {
+            Ok(false)
+        } else {
+            Err(Error::Unexpected {
+                expected: "boolean",
+                but_got: text.to_string(),
+            })
+        }
+    }
+
+    pub(crate) fn format(&self, value: bool) -> &str {
+        if value {
+            &self.true_output
+        } else {
+            &self.false_output
+        }
+    }
+}
+
+/// The conventions that structs follow to map fields to attributes, text and content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// Attributes go to fields named `@...`, text to `#text` and child elements that no other
+    /// field matches to `#content`.
+    #[default]
+    Current,
+    /// The conventions of serde-xml-rs 0.6 and 0.7 are accepted as well: attributes also go to
+    /// fields with no prefix, and text and content to a field named `$value`. Fields with no
+    /// prefix are still serialized as child elements.
+    Legacy,
+}
+
+/// How the names of elements and attributes in a document match the names of struct fields,
+/// sequence items and enum variants when deserializing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NameMatching {
+    /// Local names must be equal, and prefixes or namespaces match as configured.
+    #[default]
+    Exact,
+    /// Local names may differ in ASCII case, such as `ProductID` and `productId`, and prefixes
+    /// or namespaces match as configured.
+    IgnoreAsciiCase,
+    /// Only local names must be equal, whatever the prefix or namespace of either name.
+    LocalName,
+}
+
+/// The names that mark fields as attributes, text or content
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Markers {
+    pub(crate) attribute: String,
+    pub(crate) text: String,
+    pub(crate) content: String,
+    pub(crate) dialect: Dialect,
+}
+
+impl Default for Markers {
+    fn default() -> Self {
+        Self {
+            attribute: "@".to_string(),
+            text: TEXT.to_string(),
+            content: CONTENT.to_string(),
+            dialect: Dialect::default(),
+        }
+    }
+}
+
+impl Markers {
+    /// The name of the attribute that a field goes to, if the field is marked as an attribute.
+    /// An empty marker marks no field.
+    pub(crate) fn attribute_name<'a>(&self, field: &'a str) -> Option<&'a str> {
+        if self.attribute.is_empty() {
+            return None;
+        }
+        field.strip_prefix(self.attribute.as_str())
+    }
+
+    /// The name of the attribute that a field is read from, if the field is marked as an
+    /// attribute or could be a legacy attribute. Legacy attributes are still written as elements.
+    pub(crate) fn accepted_attribute_name<'a>(&self, field: &'a str) -> Option<&'a str> {
+        match self.attribute_name(field) {
+            Some(name) => Some(name),
+            None if self.dialect == Dialect::Legacy && self.is_element(field) => Some(field),
+            None => None,
+        }
+    }
+
+    /// Whether a field goes to a child element, rather than to an attribute, text or content
+    pub(crate) fn is_element(&self, field: &str) -> bool {
+        field != self.text
+            && !self.is_content(field)
+            && (self.attribute.is_empty() || !field.starts_with(self.attribute.as_str()))
+    }
+
+    /// Whether a field holds the content of an element
+    pub(crate) fn is_content(&self, field: &str) -> bool {
+        field == self.content || self.dialect == Dialect::Legacy && field == LEGACY_VALUE
+    }
+
+    /// The field among `fields` that holds text
+    pub(crate) fn text_field<'a>(&'a self, fields: &[&str]) -> &'a str {
+        match self.dialect {
+            Dialect::Legacy
+                if !fields.contains(&self.text.as_str()) && fields.contains(&LEGACY_VALUE) =>
+            {
+                LEGACY_VALUE
+            }
+            _ => &self.text,
+        }
+    }
+
+    /// The field among `fields` that holds child elements that no other field matches, if any
+    pub(crate) fn content_field<'f>(&self, fields: &[&'f str]) -> Option<&'f str> {
+        fields.iter().find(|field| self.is_content(field)).copied()
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct SerdeXml {
@@ -17,6 +230,17 @@ pub struct SerdeXml {
     pub(crate) parser: ParserConfig,
     pub(crate) namespaces: Namespaces,
     pub(crate) overlapping_sequences: bool,
+    pub(crate) binary_encoding: BinaryEncoding,
+    pub(crate) booleans: Booleans,
+    pub(crate) none_as_nil: bool,
+    pub(crate) markers: Markers,
+    pub(crate) name_matching: NameMatching,
+    pub(crate) root_element_name: Option<String>,
+    pub(crate) strict: bool,
+    pub(crate) verify_root_name: Option<bool>,
+    pub(crate) verify_end: Option<bool>,
+    pub(crate) deny_unknown: Option<bool>,
+    pub(crate) verify_order: Option<bool>,
 }
 
 impl Default for SerdeXml {
@@ -31,6 +255,17 @@ impl Default for SerdeXml {
                 .coalesce_characters(true),
             namespaces: Default::default(),
             overlapping_sequences: false,
+            binary_encoding: BinaryEncoding::default(),
+            booleans: Booleans::default(),
+            none_as_nil: false,
+            markers: Markers::default(),
+            name_matching: NameMatching::default(),
+            root_element_name: None,
+            strict: false,
+            verify_root_name: None,
+            verify_end: None,
+            deny_unknown: None,
+            verify_order: None,
         }
     }
 }
@@ -91,12 +326,471 @@ impl SerdeXml {
         self
     }
 
+    /// Configures how byte arrays are written in element text and attribute values, and read back.
+    /// Raw UTF-8 by default.
+    ///
+    /// ```rust
+    /// # use serde::{Deserialize, Serialize};
+    /// # use serde_xml_rs::{config::BinaryEncoding, SerdeXml};
+    /// #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    /// #[serde(rename = "file")]
+    /// struct File {
+    ///     #[serde(rename = "@checksum", with = "serde_bytes")]
+    ///     checksum: Vec<u8>,
+    ///     #[serde(with = "serde_bytes")]
+    ///     content: Vec<u8>,
+    /// }
+    /// # fn main() {
+    /// let file = File { checksum: vec![0xca, 0xfe], content: vec![0, 1, 2, 255] };
+    /// let config = SerdeXml::new().binary_encoding(BinaryEncoding::Base64);
+    /// let text = config.clone().to_string(&file).unwrap();
+    /// assert!(text.ends_with(r#"<file checksum="yv4="><content>AAEC/w==</content></file>"#));
+    /// assert_eq!(config.from_str::<File>(&text).unwrap(), file);
+    /// # }
+    /// ```
+    pub fn binary_encoding(mut self, encoding: BinaryEncoding) -> Self {
+        self.binary_encoding = encoding;
+        self
+    }
+
+    /// Configures the spellings that are accepted as booleans when deserializing, in place of
+    /// `true`, `1`, `false` and `0`.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Order {
+    ///     #[serde(rename = "@express")]
+    ///     express: bool,
+    ///     paid: bool,
+    /// }
+    /// # fn main() {
+    /// let config = SerdeXml::new()
+    ///     .boolean_values(&["yes", "y"], &["no", "n"])
+    ///     .case_insensitive_booleans(true);
+    /// let order = config
+    ///     .from_str::<Order>(r#"<order express="Y"><paid>No</paid></order>"#)
+    ///     .unwrap();
+    /// assert_eq!(order, Order { express: true, paid: false });
+    /// # }
+    /// ```
+    ///
+    /// HTML-style attributes such as `checked="checked"`, which are false when absent, work with
+    /// `true_values` of `["checked"]` and `#[serde(default)]` on the field.
+    pub fn boolean_values(mut self, true_values: &[&str], false_values: &[&str]) -> Self {
+        self.booleans.true_values = true_values.iter().map(|value| value.to_string()).collect();
+        self.booleans.false_values = false_values.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    /// Configures whether booleans are matched without regard to ASCII case when deserializing.
+    /// Disabled by default.
+    pub fn case_insensitive_booleans(mut self, enabled: bool) -> Self {
+        self.booleans.case_insensitive = enabled;
+        self
+    }
+
+    /// Configures the spellings of booleans when serializing. `true` and `false` by default.
+    ///
+    /// ```rust
+    /// # use serde::Serialize;
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Serialize)]
+    /// #[serde(rename = "order")]
+    /// struct Order {
+    ///     #[serde(rename = "@express")]
+    ///     express: bool,
+    ///     paid: bool,
+    /// }
+    /// # fn main() {
+    /// let text = SerdeXml::new()
+    ///     .boolean_output("Y", "N")
+    ///     .to_string(&Order { express: true, paid: false })
+    ///     .unwrap();
+    /// assert!(text.ends_with(r#"<order express="Y"><paid>N</paid></order>"#));
+    /// # }
+    /// ```
+    pub fn boolean_output<S: ToString>(mut self, true_value: S, false_value: S) -> Self {
+        self.booleans.true_output = true_value.to_string();
+        self.booleans.false_output = false_value.to_string();
+        self
+    }
+
+    /// Configures whether `None` values of elements are written as explicit nulls, such as
+    /// `<field xsi:nil="true"/>`, instead of being left out. The `xsi` namespace is then declared on
+    /// the root element. Disabled by default.
+    ///
+    /// Elements marked with `xsi:nil="true"` are always deserialized as `None`.
+    ///
+    /// ```rust
+    /// # use serde::{Deserialize, Serialize};
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    /// #[serde(rename = "person")]
+    /// struct Person {
+    ///     name: String,
+    ///     email: Option<String>,
+    /// }
+    /// # fn main() {
+    /// let config = SerdeXml::new().none_as_nil(true);
+    /// let person = Person { name: "Ada".to_string(), email: None };
+    /// let text = config.clone().to_string(&person).unwrap();
+    /// assert!(text.ends_with(
+    ///     r#"<person xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><name>Ada</name><email xsi:nil="true" /></person>"#
+    /// ));
+    /// assert_eq!(config.from_str::<Person>(&text).unwrap(), person);
+    /// # }
+    /// ```
+    pub fn none_as_nil(mut self, enabled: bool) -> Self {
+        self.none_as_nil = enabled;
+        self
+    }
+
+    /// Configures the prefix that marks fields as attributes. `@` by default. An empty marker
+    /// marks no field, so every field goes to an element.
+    ///
+    /// ```rust
+    /// # use serde::{Deserialize, Serialize};
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    /// #[serde(rename = "item")]
+    /// struct Item {
+    ///     #[serde(rename = "_id")]
+    ///     id: u32,
+    ///     #[serde(rename = "value")]
+    ///     text: String,
+    /// }
+    /// # fn main() {
+    /// let config = SerdeXml::new().attribute_marker("_").text_field("value");
+    /// let item = Item { id: 1, text: "abc".to_string() };
+    /// let text = config.clone().to_string(&item).unwrap();
+    /// assert!(text.ends_with(r#"<item id="1">abc</item>"#));
+    /// assert_eq!(config.from_str::<Item>(&text).unwrap(), item);
+    /// # }
+    /// ```
+    pub fn attribute_marker<S: ToString>(mut self, marker: S) -> Self {
+        self.markers.attribute = marker.to_string();
+        self
+    }
+
+    /// Configures the name of the field that holds the text content of an element. `#text` by
+    /// default.
+    pub fn text_field<S: ToString>(mut self, name: S) -> Self {
+        self.markers.text = name.to_string();
+        self
+    }
+
+    /// Configures the name of the field that holds the child elements that no other field
+    /// matches, such as a sequence of choices. `#content` by default.
+    pub fn content_field<S: ToString>(mut self, name: S) -> Self {
+        self.markers.content = name.to_string();
+        self
+    }
+
+    /// Configures the conventions that structs follow. [Dialect::Legacy] accepts structs written
+    /// for serde-xml-rs 0.6 and 0.7 alongside current ones, to migrate them gradually.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::{config::Dialect, SerdeXml};
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Item {
+    ///     id: u32,
+    ///     #[serde(rename = "$value")]
+    ///     name: String,
+    /// }
+    /// # fn main() {
+    /// let item = SerdeXml::new()
+    ///     .dialect(Dialect::Legacy)
+    ///     .from_str::<Item>(r#"<item id="1">abc</item>"#)
+    ///     .unwrap();
+    /// assert_eq!(item, Item { id: 1, name: "abc".to_string() });
+    /// # }
+    /// ```
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.markers.dialect = dialect;
+        self
+    }
+
+    /// Configures how the names of elements and attributes match struct fields, sequence items,
+    /// enum variants and the expected root element when deserializing, for documents from
+    /// producers that disagree on case or prefixes. [NameMatching::Exact] by default. Maps and
+    /// self-describing types see names as they are.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::{config::NameMatching, SerdeXml};
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Product {
+    ///     #[serde(rename = "productId")]
+    ///     product_id: u32,
+    /// }
+    /// # fn main() {
+    /// let text = "<product><ProductID>1</ProductID></product>";
+    /// let product = SerdeXml::new()
+    ///     .name_matching(NameMatching::IgnoreAsciiCase)
+    ///     .from_str::<Product>(text)
+    ///     .unwrap();
+    /// assert_eq!(product, Product { product_id: 1 });
+    /// # }
+    /// ```
+    pub fn name_matching(mut self, policy: NameMatching) -> Self {
+        self.name_matching = policy;
+        self
+    }
+
+    /// Configures the name of the root element for values that have no name of their own:
+    /// primitives, units, sequences, tuples and maps. Without it, these values cannot be
+    /// serialized in the document root.
+    ///
+    /// The items of a sequence are written to `<item>` child elements, and the entries of a map
+    /// to child elements named after their keys. Both are read back the same way.
+    ///
+    /// ```rust
+    /// # use serde_xml_rs::SerdeXml;
+    /// # use std::collections::BTreeMap;
+    /// # fn main() {
+    /// let config = SerdeXml::new().root_element_name("root");
+    /// let text = config.clone().to_string(&vec![1, 2]).unwrap();
+    /// assert!(text.ends_with("<root><item>1</item><item>2</item></root>"));
+    /// assert_eq!(config.clone().from_str::<Vec<i32>>(&text).unwrap(), vec![1, 2]);
+    ///
+    /// let map = BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    /// let text = config.clone().to_string(&map).unwrap();
+    /// assert!(text.ends_with("<root><a>1</a><b>2</b></root>"));
+    /// assert_eq!(config.from_str::<BTreeMap<String, i32>>(&text).unwrap(), map);
+    /// # }
+    /// ```
+    pub fn root_element_name<S: ToString>(mut self, name: S) -> Self {
+        self.root_element_name = Some(name.to_string());
+        self
+    }
+
+    /// Enables strict checks of the documents being deserialized, which each default to on in
+    /// strict mode and can be configured one by one:
+    /// - [verify_root_name](Self::verify_root_name())
+    /// - [verify_end](Self::verify_end())
+    /// - [deny_unknown](Self::deny_unknown())
+    /// - [verify_order](Self::verify_order())
+    ///
+    /// Disabled by default.
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Configures whether the root element must be named after the type being deserialized,
+    /// that is its name in serde, for structs, enums, and newtype, tuple and unit structs. Other
+    /// types are checked against the configured [root element name](Self::root_element_name()),
+    /// if any. A document with another root element fails with [Error::UnexpectedRoot]. On in
+    /// [strict](Self::strict()) mode, and off otherwise.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// #[serde(rename = "order")]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    /// # fn main() {
+    /// let text = "<invoice><id>1</id></invoice>";
+    /// assert!(SerdeXml::new().from_str::<Order>(text).is_ok());
+    /// assert!(SerdeXml::new().verify_root_name(true).from_str::<Order>(text).is_err());
+    /// assert!(SerdeXml::new().strict(true).from_str::<Order>(text).is_err());
+    /// # }
+    /// ```
+    pub fn verify_root_name(mut self, enabled: bool) -> Self {
+        self.verify_root_name = Some(enabled);
+        self
+    }
+
+    pub(crate) fn verifies_root_name(&self) -> bool {
+        self.verify_root_name.unwrap_or(self.strict)
+    }
+
+    /// Configures whether only whitespace, comments and processing instructions may follow the
+    /// root element, so that a second root element or other trailing content is an error, as
+    /// with [Deserializer::end]. It applies when deserializing a single document, not to
+    /// [streams](Self::iter_from_reader()). On in [strict](Self::strict()) mode, and off
+    /// otherwise.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    /// # fn main() {
+    /// let text = "<order><id>1</id></order><order><id>2</id></order>";
+    /// assert!(SerdeXml::new().from_str::<Order>(text).is_ok());
+    /// assert!(SerdeXml::new().verify_end(true).from_str::<Order>(text).is_err());
+    /// # }
+    /// ```
+    pub fn verify_end(mut self, enabled: bool) -> Self {
+        self.verify_end = Some(enabled);
+        self
+    }
+
+    pub(crate) fn verifies_end(&self) -> bool {
+        self.verify_end.unwrap_or(self.strict)
+    }
+
+    /// Configures whether elements, attributes and text that are not deserialized to any field
+    /// are errors, instead of being skipped. This applies to the whole document: nested
+    /// structs, enum variants, maps, and elements deserialized as primitives, whose attributes
+    /// are otherwise dropped. The error gives the path to the unknown content. Attributes in the
+    /// `xsi` namespace, such as `xsi:schemaLocation`, are allowed anywhere. On in
+    /// [strict](Self::strict()) mode, and off otherwise.
+    ///
+    /// Structs with flattened fields need `#[serde(deny_unknown_fields)]` instead, see
+    /// [flattened structs](crate#flattened-structs-and-maps).
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    /// # fn main() {
+    /// let text = "<order><id>1</id><note>urgent</note></order>";
+    /// assert!(SerdeXml::new().from_str::<Order>(text).is_ok());
+    /// let error = SerdeXml::new().deny_unknown(true).from_str::<Order>(text).unwrap_err();
+    /// assert_eq!(error.path().unwrap().to_string(), "/order/note");
+    /// # }
+    /// ```
+    pub fn deny_unknown(mut self, enabled: bool) -> Self {
+        self.deny_unknown = Some(enabled);
+        self
+    }
+
+    pub(crate) fn denies_unknown(&self) -> bool {
+        self.deny_unknown.unwrap_or(self.strict)
+    }
+
+    /// Configures whether the child elements of structs must appear in the order of the struct
+    /// fields, as with an `xs:sequence` in XML Schema, with the elements of a repeated field
+    /// next to each other. Missing optional fields may be skipped. An element out of order
+    /// fails with [Error::UnexpectedOrder], which names the field it must come before.
+    /// Sequences are then not gathered from [overlapping](Self::overlapping_sequences())
+    /// elements. On in [strict](Self::strict()) mode, and off otherwise.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Order {
+    ///     id: u32,
+    ///     customer: String,
+    /// }
+    /// # fn main() {
+    /// let text = "<order><customer>ACME</customer><id>1</id></order>";
+    /// assert!(SerdeXml::new().from_str::<Order>(text).is_ok());
+    /// let error = SerdeXml::new().verify_order(true).from_str::<Order>(text).unwrap_err();
+    /// assert_eq!(error.path().unwrap().to_string(), "/order/id");
+    /// # }
+    /// ```
+    pub fn verify_order(mut self, enabled: bool) -> Self {
+        self.verify_order = Some(enabled);
+        self
+    }
+
+    pub(crate) fn verifies_order(&self) -> bool {
+        self.verify_order.unwrap_or(self.strict)
+    }
+
     pub fn from_str<'de, T: Deserialize<'de>>(self, s: &str) -> Result<T> {
         self.from_reader(s.as_bytes())
     }
 
+    pub fn from_slice<'de, T: Deserialize<'de>>(self, input: &'de [u8]) -> Result<T> {
+        Deserializer::from_config_slice(self, input).deserialize_document()
+    }
+
     pub fn from_reader<'de, T: Deserialize<'de>, R: Read>(self, reader: R) -> Result<T> {
-        T::deserialize(&mut Deserializer::from_config(self, reader))
+        Deserializer::from_config(self, reader).deserialize_document()
+    }
+
+    /// Deserialize a `T` from a document whose root element must have the name `root`, chosen
+    /// at runtime. See [Deserializer::with_root_element_name].
+    pub fn from_str_with_root<'de, T: Deserialize<'de>>(self, root: &str, s: &str) -> Result<T> {
+        Deserializer::from_config(self, s.as_bytes())
+            .with_root_element_name(root)
+            .deserialize_document()
+    }
+
+    /// Deserialize a `T` from a reader, whose root element must have the name `root`, chosen at
+    /// runtime. See [Deserializer::with_root_element_name].
+    pub fn from_reader_with_root<'de, T: Deserialize<'de>, R: Read>(
+        self,
+        root: &str,
+        reader: R,
+    ) -> Result<T> {
+        Deserializer::from_config(self, reader)
+            .with_root_element_name(root)
+            .deserialize_document()
+    }
+
+    /// Deserialize a `T`, along with a report of the content that no field deserialized, in
+    /// document order. See [Deserializer::with_report].
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::{IgnoredKind, SerdeXml};
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    /// # fn main() {
+    /// let text = "<order>\n  <id>1</id>\n  <note>urgent</note>\n</order>";
+    /// let (order, ignored) = SerdeXml::new().from_str_with_report::<Order>(text).unwrap();
+    /// assert_eq!(order, Order { id: 1 });
+    /// assert_eq!(ignored.len(), 1);
+    /// assert_eq!(ignored[0].kind, IgnoredKind::Element);
+    /// assert_eq!(ignored[0].path.to_string(), "/order/note");
+    /// assert_eq!(ignored[0].position.unwrap().line, 3);
+    /// # }
+    /// ```
+    pub fn from_str_with_report<'de, T: Deserialize<'de>>(
+        self,
+        s: &str,
+    ) -> Result<(T, Vec<Ignored>)> {
+        let mut deserializer = Deserializer::from_config(self, s.as_bytes()).with_report();
+        let value = deserializer.deserialize_document()?;
+        Ok((value, deserializer.take_ignored()))
+    }
+
+    /// Deserialize a `T` from a reader, along with a report of the content that no field
+    /// deserialized. See [SerdeXml::from_str_with_report].
+    pub fn from_reader_with_report<'de, T: Deserialize<'de>, R: Read>(
+        self,
+        reader: R,
+    ) -> Result<(T, Vec<Ignored>)> {
+        let mut deserializer = Deserializer::from_config(self, reader).with_report();
+        let value = deserializer.deserialize_document()?;
+        Ok((value, deserializer.take_ignored()))
+    }
+
+    /// Iterate over the consecutive root elements of the input, deserializing each as a `T`.
+    /// See [StreamDeserializer].
+    pub fn iter_from_reader<'de, T: Deserialize<'de>, R: Read>(
+        self,
+        reader: R,
+    ) -> StreamDeserializer<'de, R, OwnedInput, T> {
+        Deserializer::from_config(self, reader).into_iter()
+    }
+
+    /// Iterate over the elements selected by a path of element names separated by slashes,
+    /// such as `rss/channel/item`, deserializing each as a `T`. See [StreamDeserializer].
+    pub fn iter_path_from_reader<'de, T: Deserialize<'de>, R: Read>(
+        self,
+        path: &str,
+        reader: R,
+    ) -> StreamDeserializer<'de, R, OwnedInput, T> {
+        Deserializer::from_config(self, reader).into_iter_path(path)
     }
 
     pub fn to_string<S: Serialize>(self, value: &S) -> Result<String> {
@@ -113,11 +807,49 @@ impl SerdeXml {
         let mut s = Serializer::from_config(self, writer);
         value.serialize(&mut s)
     }
+
+    /// Serialize a value to a string, naming the root element `root` instead of after the type
+    /// of the value.
+    ///
+    /// ```rust
+    /// # use serde::{Deserialize, Serialize};
+    /// # use serde_xml_rs::SerdeXml;
+    /// #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    /// struct Message {
+    ///     id: u32,
+    /// }
+    /// # fn main() {
+    /// let message = Message { id: 1 };
+    /// let text = SerdeXml::new().to_string_with_root("cancel", &message).unwrap();
+    /// assert!(text.ends_with("<cancel><id>1</id></cancel>"));
+    /// assert_eq!(
+    ///     SerdeXml::new().from_str_with_root::<Message>("cancel", &text).unwrap(),
+    ///     message
+    /// );
+    /// assert!(SerdeXml::new().from_str_with_root::<Message>("order", &text).is_err());
+    /// # }
+    /// ```
+    pub fn to_string_with_root<S: Serialize>(self, root: &str, value: &S) -> Result<String> {
+        let mut buffer = Vec::new();
+        self.to_writer_with_root(&mut buffer, root, value)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Serialize a value to a writer, naming the root element `root` instead of after the type
+    /// of the value.
+    pub fn to_writer_with_root<W, S>(self, writer: W, root: &str, value: &S) -> Result<()>
+    where
+        W: Write,
+        S: Serialize,
+    {
+        let mut s = Serializer::from_config(self, writer).with_root_element_name(root);
+        value.serialize(&mut s)
+    }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct Namespaces {
-    mapping: BTreeMap<String, String>,
+    pub(crate) mapping: BTreeMap<String, String>,
 }
 
 impl Namespaces {
@@ -134,6 +866,14 @@ impl Namespaces {
         self.mapping.get(prefix.as_ref())
     }
 
+    /// The prefix configured for a namespace URI
+    pub(crate) fn prefix_for(&self, uri: &str) -> Option<&str> {
+        self.mapping
+            .iter()
+            .find(|(_, name)| *name == uri)
+            .map(|(prefix, _)| prefix.as_str())
+    }
+
     pub(crate) fn add_to_start_element<'a>(
         &self,
         mut start_element_builder: StartElementBuilder<'a>,
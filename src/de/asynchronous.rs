@@ -0,0 +1,142 @@
+use crate::{config::SerdeXml, error::Result};
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{io::AsyncRead, sync::mpsc, task};
+use tokio_util::io::SyncIoBridge;
+
+/// Number of records that are deserialized ahead of the consumer of an [AsyncStreamDeserializer]
+const RECORDS_AHEAD: usize = 16;
+
+/// Deserialize a whole document from an asynchronous reader, with the default configuration.
+/// See [SerdeXml::from_async_reader].
+pub async fn from_async_reader<T, R>(reader: R) -> Result<T>
+where
+    T: DeserializeOwned + Send + 'static,
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    SerdeXml::default().from_async_reader(reader).await
+}
+
+impl SerdeXml {
+    /// Deserialize a whole document from an asynchronous reader.
+    ///
+    /// The document is parsed as it arrives, on a blocking thread of the Tokio runtime, so that
+    /// it is never buffered in full. This must be called from within a Tokio runtime.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// #[derive(Debug, Deserialize)]
+    /// struct Item {
+    ///     name: String,
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let text: &[u8] = b"<item><name>hello</name></item>";
+    /// let item: Item = serde_xml_rs::SerdeXml::new()
+    ///     .from_async_reader(text)
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(item.name, "hello");
+    /// # }
+    /// ```
+    pub async fn from_async_reader<T, R>(self, reader: R) -> Result<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        task::spawn_blocking(move || self.from_reader(SyncIoBridge::new(reader)))
+            .await
+            .unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()))
+    }
+
+    /// Stream the consecutive root elements of an asynchronous reader, deserializing each as a
+    /// `T`. See [AsyncStreamDeserializer].
+    pub fn iter_from_async_reader<T, R>(self, reader: R) -> AsyncStreamDeserializer<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        AsyncStreamDeserializer::new(self, "", reader)
+    }
+
+    /// Stream the elements of an asynchronous reader that are selected by a path of element
+    /// names separated by slashes, such as `rss/channel/item`, deserializing each as a `T`. See
+    /// [AsyncStreamDeserializer].
+    pub fn iter_path_from_async_reader<T, R>(
+        self,
+        path: &str,
+        reader: R,
+    ) -> AsyncStreamDeserializer<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        AsyncStreamDeserializer::new(self, path, reader)
+    }
+}
+
+/// A stream over the consecutive root elements of a document, or over the elements selected by a
+/// path, read from an asynchronous reader and deserialized as values of type `T`.
+///
+/// The records are deserialized as they arrive by a
+/// [StreamDeserializer](crate::de::StreamDeserializer) that runs on a blocking thread of the
+/// Tokio runtime, and that stops when the stream is dropped.
+///
+/// ```rust
+/// # use serde::Deserialize;
+/// # use std::future::poll_fn;
+/// # use std::pin::Pin;
+/// # use futures_core::Stream;
+/// #[derive(Debug, Deserialize)]
+/// struct Item {
+///     title: String,
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let text: &[u8] = b"<rss><channel><item><title>a</title></item><item><title>b</title></item></channel></rss>";
+/// let mut items = serde_xml_rs::SerdeXml::new().iter_path_from_async_reader::<Item, _>("rss/channel/item", text);
+/// let mut titles = Vec::new();
+/// while let Some(item) = poll_fn(|cx| Pin::new(&mut items).poll_next(cx)).await {
+///     titles.push(item.unwrap().title);
+/// }
+/// assert_eq!(titles, vec!["a", "b"]);
+/// # }
+/// ```
+pub struct AsyncStreamDeserializer<T> {
+    receiver: mpsc::Receiver<Result<T>>,
+}
+
+impl<T: DeserializeOwned + Send + 'static> AsyncStreamDeserializer<T> {
+    /// Start deserializing the records of `reader`. This must be called from within a Tokio
+    /// runtime.
+    pub fn new<R>(config: SerdeXml, path: &str, reader: R) -> Self
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(RECORDS_AHEAD);
+        let path = path.to_string();
+        task::spawn_blocking(move || {
+            let records = config.iter_path_from_reader::<T, _>(&path, SyncIoBridge::new(reader));
+            for record in records {
+                if sender.blocking_send(record).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { receiver }
+    }
+}
+
+impl<T> Stream for AsyncStreamDeserializer<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
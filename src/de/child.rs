@@ -1,38 +1,65 @@
 use super::{
+    input::Input,
     map::MapAccess,
     plain::PlainTextDeserializer,
-    reader::{ChildReader, Event, Reader},
-    seq::SeqAccess,
+    reader::{ChildReader, Event, Reader, Text},
+    replay,
+    report::IgnoredKind,
+    seq::{SeqAccess, TupleAccess},
     var::EnumAccess,
 };
-use crate::error::{Error, Result};
+use crate::error::{Error, Result, Step};
 use log::trace;
 use serde::de::Visitor;
 use std::io::Read;
 
-pub struct ChildDeserializer<'a, R: Read> {
-    reader: ChildReader<'a, R>,
+pub struct ChildDeserializer<'a, R: Read, B> {
+    reader: ChildReader<'a, R, B>,
     element_name: Option<String>,
+    /// Whether the step to the element that is read goes into error paths and the report, unless
+    /// the caller adds it
+    steps: bool,
+    /// Whether the element is recorded as unchecked if it is deserialized as any type
+    buffered: bool,
 }
 
-impl<'a, R: Read> ChildDeserializer<'a, R> {
-    pub fn new(reader: ChildReader<'a, R>) -> Self {
+impl<'a, R: Read, B> ChildDeserializer<'a, R, B> {
+    pub fn new(reader: ChildReader<'a, R, B>) -> Self {
         Self {
             reader,
             element_name: None,
+            steps: true,
+            buffered: false,
         }
     }
 
-    pub fn new_with_element_name(reader: ChildReader<'a, R>, element_name: String) -> Self {
+    pub fn new_with_element_name(reader: ChildReader<'a, R, B>, element_name: String) -> Self {
         Self {
             reader,
             element_name: Some(element_name),
+            steps: true,
+            buffered: false,
         }
     }
 
+    /// Leave the step to the element out, for callers that add their own, such as sequences
+    /// with the index of each item
+    pub fn without_step(mut self) -> Self {
+        self.steps = false;
+        self
+    }
+
+    /// Record the element as unchecked if serde buffers it, by deserializing it as any type
+    pub fn buffered(mut self, buffered: bool) -> Self {
+        self.buffered = buffered;
+        self
+    }
+}
+
+impl<'de, R: Read, B: Input<'de, R>> ChildDeserializer<'_, R, B> {
     pub fn maybe_start_element(&mut self) -> Result<()> {
         if self.element_name.is_some() {
-            self.reader.start_element()?;
+            self.reader.start_element_without_attributes()?;
         }
         Ok(())
     }
@@ -43,28 +70,80 @@ impl<'a, R: Read> ChildDeserializer<'a, R> {
         }
         Ok(())
     }
+
+    /// Run `f`, which reads the next element if `into_element`, adding the step to that element
+    /// to the path of its errors and of the content it skips
+    fn stepped<T>(
+        &mut self,
+        into_element: bool,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        if !(self.steps && into_element) {
+            return f(self);
+        }
+        let position = self.reader.position()?;
+        let step = match self.reader.peek()? {
+            Event::StartElement(element) => Step::element(element.qname()),
+            _ => return f(self),
+        };
+        let mark = self.reader.collector.enter(|| step.clone(), position);
+        let value = f(self).map_err(|error| error.at(position).within(step));
+        self.reader.collector.leave(mark);
+        value
+    }
 }
 
 macro_rules! deserialize_type {
     ($deserialize:ident => $visit:ident) => {
         fn $deserialize<V: ::serde::de::Visitor<'de>>(mut self, visitor: V) -> Result<V::Value> {
             trace!("{}", stringify!($deserialize:ident));
-            self.maybe_start_element()?;
-            let value = self.reader.chars()?.parse()?;
-            self.maybe_end_element()?;
-            visitor.$visit(value)
+            self.stepped(self.element_name.is_some(), |this| {
+                this.maybe_start_element()?;
+                let value = this.reader.chars()?.parse()?;
+                this.maybe_end_element()?;
+                visitor.$visit(value)
+            })
         }
     };
 }
 
-impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
+impl<'de, R: Read, B: Input<'de, R>> serde::Deserializer<'de> for ChildDeserializer<'_, R, B> {
     type Error = Error;
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::Unsupported("deserialize any"))
+        trace!("any");
+        if self.buffered {
+            let position = self.reader.position()?;
+            let step = match self.reader.peek()? {
+                Event::StartElement(element) => Some(Step::element(element.qname())),
+                _ => None,
+            };
+            self.reader
+                .collector
+                .record(IgnoredKind::Unchecked, step, position);
+        }
+        if self.element_name.is_some() {
+            if self.reader.is_leaf_element()? {
+                self.deserialize_string(visitor)
+            } else {
+                self.stepped(true, |this| {
+                    let element = this.reader.start_element()?;
+                    let value = visitor
+                        .visit_map(MapAccess::new_any(this.reader.child(), element.attributes))?;
+                    this.reader.end_element()?;
+                    Ok(value)
+                })
+            }
+        } else {
+            if self.reader.is_text_content()? {
+                self.deserialize_string(visitor)
+            } else {
+                visitor.visit_map(MapAccess::new_any(self.reader.child(), vec![]))
+            }
+        }
     }
 
     fn deserialize_bool<V>(mut self, visitor: V) -> Result<V::Value>
@@ -72,10 +151,12 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         V: Visitor<'de>,
     {
         trace!("bool");
-        self.maybe_start_element()?;
-        let value = self.reader.bool()?;
-        self.maybe_end_element()?;
-        visitor.visit_bool(value)
+        self.stepped(self.element_name.is_some(), |this| {
+            this.maybe_start_element()?;
+            let value = this.reader.config.booleans.parse(&this.reader.chars()?)?;
+            this.maybe_end_element()?;
+            visitor.visit_bool(value)
+        })
     }
 
     deserialize_type!(deserialize_i8 => visit_i8);
@@ -110,33 +191,47 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         V: Visitor<'de>,
     {
         trace!("string");
-        self.maybe_start_element()?;
-        let text = if matches!(self.reader.peek()?, Event::Text(_)) {
-            self.reader.chars()?
-        } else {
-            "".to_string()
-        };
-        let value = visitor.visit_string::<Self::Error>(text)?;
-        self.maybe_end_element()?;
-        Ok(value)
+        self.stepped(self.element_name.is_some(), |this| {
+            this.maybe_start_element()?;
+            let text = if matches!(this.reader.peek()?, Event::Text(_)) {
+                this.reader.text()?
+            } else {
+                Text::default()
+            };
+            let value = match this.reader.lend(&text.span) {
+                Some(borrowed) => visitor.visit_borrowed_str::<Error>(borrowed)?,
+                None => visitor.visit_string::<Error>(text.value)?,
+            };
+            this.maybe_end_element()?;
+            Ok(value)
+        })
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_bytes<V>(mut self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::Unsupported("bytes"))
+        trace!("bytes");
+        self.stepped(self.element_name.is_some(), |this| {
+            this.maybe_start_element()?;
+            let text = if matches!(this.reader.peek()?, Event::Text(_)) {
+                this.reader.text()?
+            } else {
+                Text::default()
+            };
+            let value = PlainTextDeserializer::new(&text.value, this.reader.config)
+                .borrowing(this.reader.lend(&text.span))
+                .deserialize_bytes(visitor)?;
+            this.maybe_end_element()?;
+            Ok(value)
+        })
     }
 
-    fn deserialize_byte_buf<V>(mut self, visitor: V) -> Result<V::Value>
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        trace!("byte buffer");
-        self.maybe_start_element()?;
-        let value = visitor.visit_byte_buf::<Self::Error>(self.reader.chars()?.into_bytes())?;
-        self.maybe_end_element()?;
-        Ok(value)
+        self.deserialize_bytes(visitor)
     }
 
     fn deserialize_option<V>(mut self, visitor: V) -> Result<V::Value>
@@ -145,10 +240,14 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
     {
         trace!("option");
         if self.element_name.is_some() {
-            if matches!(self.reader.peek()?, Event::StartElement(_)) {
-                visitor.visit_some(self)
-            } else {
-                visitor.visit_none()
+            match self.reader.peek()? {
+                Event::StartElement(element) if element.is_nil() => {
+                    trace!("nil");
+                    self.reader.ignore()?;
+                    visitor.visit_none()
+                }
+                Event::StartElement(_) => visitor.visit_some(self),
+                _ => visitor.visit_none(),
             }
         } else if matches!(self.reader.peek()?, Event::EndElement) {
             visitor.visit_none()
@@ -165,10 +264,12 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         if self.element_name.is_none() {
             visitor.visit_unit()
         } else {
-            self.reader.start_element()?;
-            let value = visitor.visit_unit::<Self::Error>()?;
-            self.reader.end_element()?;
-            Ok(value)
+            self.stepped(true, |this| {
+                this.reader.start_element_without_attributes()?;
+                let value = visitor.visit_unit::<Error>()?;
+                this.reader.end_element()?;
+                Ok(value)
+            })
         }
     }
 
@@ -180,12 +281,25 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         self.deserialize_unit(visitor)
     }
 
-    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(mut self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
         trace!("newtype struct '{name}'");
-
+        if name == replay::REPLAY {
+            return self.stepped(self.element_name.is_some(), |this| {
+                match this.element_name.take() {
+                    Some(element_name) => {
+                        let events = this.reader.capture_element()?;
+                        replay::visit_element(visitor, this.reader.config, element_name, events)
+                    }
+                    None => {
+                        let events = this.reader.capture_content()?;
+                        replay::visit_content(visitor, this.reader.config, events)
+                    }
+                }
+            });
+        }
         visitor.visit_newtype_struct(self)
     }
 
@@ -202,11 +316,12 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         V: Visitor<'de>,
     {
         trace!("tuple");
-        self.maybe_start_element()?;
-        let text = self.reader.chars()?;
-        let value = visitor.visit_seq(PlainTextDeserializer::new(&text))?;
-        self.maybe_end_element()?;
-        Ok(value)
+        self.stepped(self.element_name.is_some(), |this| {
+            this.maybe_start_element()?;
+            let value = visitor.visit_seq(TupleAccess::new(this.reader.child()))?;
+            this.maybe_end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_tuple_struct<V>(
@@ -227,10 +342,16 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         V: Visitor<'de>,
     {
         trace!("map");
-        self.maybe_start_element()?;
-        let value = visitor.visit_map(MapAccess::new_map(self.reader.child()))?;
-        self.maybe_end_element()?;
-        Ok(value)
+        self.stepped(self.element_name.is_some(), |this| {
+            let attributes = if this.element_name.is_some() {
+                this.reader.start_element()?.attributes
+            } else {
+                vec![]
+            };
+            let value = visitor.visit_map(MapAccess::new_map(this.reader.child(), attributes))?;
+            this.maybe_end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_struct<V>(
@@ -243,30 +364,35 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         V: Visitor<'de>,
     {
         trace!("struct '{name}'");
-        let element = self.reader.start_element()?;
-        let value = visitor.visit_map(MapAccess::new_struct(
-            self.reader.child(),
-            element.attributes,
-            fields,
-        ))?;
-        self.reader.end_element()?;
-        Ok(value)
+        self.stepped(true, |this| {
+            let element = this.reader.start_element()?;
+            let value = visitor.visit_map(MapAccess::new_struct(
+                this.reader.child(),
+                element.attributes,
+                fields,
+            ))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_enum<V>(
         mut self,
         name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
         trace!("enum '{name}'");
-        self.maybe_start_element()?;
-        let value = visitor.visit_enum(EnumAccess::new(self.reader.child()))?;
-        self.maybe_end_element()?;
-        Ok(value)
+        // Without an element name, the next element is the variant, which steps into itself
+        self.stepped(self.element_name.is_some(), |this| {
+            this.maybe_start_element()?;
+            let value = visitor.visit_enum(EnumAccess::new(this.reader.child(), variants))?;
+            this.maybe_end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
@@ -282,7 +408,19 @@ impl<'de, R: Read> serde::Deserializer<'de> for ChildDeserializer<'_, R> {
         V: Visitor<'de>,
     {
         trace!("ignoring {:?}", self.reader.peek()?);
-        self.reader.ignore()?;
-        visitor.visit_unit()
+        self.stepped(true, |this| {
+            if this.reader.config.denies_unknown() {
+                return Err(Error::UnknownContent(match this.reader.peek()? {
+                    Event::StartElement(element) => format!("element '{}'", element.qname()),
+                    event => event.to_string(),
+                }));
+            }
+            let position = this.reader.position()?;
+            this.reader
+                .collector
+                .record(IgnoredKind::Element, None, position);
+            this.reader.ignore()?;
+            visitor.visit_unit()
+        })
     }
 }
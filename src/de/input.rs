@@ -0,0 +1,85 @@
+use std::ops::Range;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// How the deserializer can lend text from its input to the values it deserializes.
+///
+/// This trait is sealed: it is implemented by [OwnedInput] and [BorrowedInput] only.
+pub trait Input<'de, R>: private::Sealed {
+    /// Range of the input read since the previous call, given the reader after an event.
+    #[doc(hidden)]
+    fn region(&mut self, reader: &R) -> Option<Range<usize>>;
+
+    /// Locate `text` verbatim in the given region of the input.
+    #[doc(hidden)]
+    fn find(&self, region: &Range<usize>, text: &str) -> Option<Range<usize>>;
+
+    /// Borrow the input located by [Input::find].
+    #[doc(hidden)]
+    fn lend(&self, span: &Range<usize>) -> Option<&'de str>;
+}
+
+/// Input that is read from a stream, so that every string is owned.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OwnedInput;
+
+impl private::Sealed for OwnedInput {}
+
+impl<'de, R> Input<'de, R> for OwnedInput {
+    fn region(&mut self, _reader: &R) -> Option<Range<usize>> {
+        None
+    }
+
+    fn find(&self, _region: &Range<usize>, _text: &str) -> Option<Range<usize>> {
+        None
+    }
+
+    fn lend(&self, _span: &Range<usize>) -> Option<&'de str> {
+        None
+    }
+}
+
+/// Input that is held in memory, so that text and attribute values can be borrowed from it
+/// whenever they appear verbatim, that is without entities or character references.
+#[derive(Clone, Copy, Debug)]
+pub struct BorrowedInput<'de> {
+    input: &'de [u8],
+    consumed: usize,
+}
+
+/// Number of bytes before the end of the previous event that are searched again, in case the
+/// parser read them ahead of time.
+const LOOKBEHIND: usize = 4;
+
+impl<'de> BorrowedInput<'de> {
+    pub fn new(input: &'de [u8]) -> Self {
+        Self { input, consumed: 0 }
+    }
+}
+
+impl private::Sealed for BorrowedInput<'_> {}
+
+impl<'de> Input<'de, &'de [u8]> for BorrowedInput<'de> {
+    fn region(&mut self, reader: &&'de [u8]) -> Option<Range<usize>> {
+        let start = self.consumed.saturating_sub(LOOKBEHIND);
+        self.consumed = self.input.len() - reader.len();
+        Some(start..self.consumed)
+    }
+
+    fn find(&self, region: &Range<usize>, text: &str) -> Option<Range<usize>> {
+        let needle = text.as_bytes();
+        if needle.is_empty() {
+            return Some(region.start..region.start);
+        }
+        self.input[region.clone()]
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .map(|offset| region.start + offset..region.start + offset + needle.len())
+    }
+
+    fn lend(&self, span: &Range<usize>) -> Option<&'de str> {
+        std::str::from_utf8(&self.input[span.clone()]).ok()
+    }
+}
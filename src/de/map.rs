@@ -1,33 +1,63 @@
 use super::{
     child::ChildDeserializer,
+    input::Input,
     plain::PlainTextDeserializer,
-    reader::{Attribute, ChildReader, Event, Reader},
+    reader::{Attribute, ChildReader, Element, Event, Reader},
+    report::IgnoredKind,
+    seq::SeqAccess,
 };
 use crate::{
-    config::{CONTENT, TEXT},
-    error::{Error, Result},
+    config::SerdeXml,
+    error::{Error, Result, Step},
 };
 use log::trace;
-use serde::de::IntoDeserializer;
+use serde::{
+    de::{
+        value::{SeqAccessDeserializer, SeqDeserializer},
+        IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any,
+};
 use std::{io::Read, iter::Peekable};
 
-pub struct MapAccess<'a, R: Read> {
-    reader: ChildReader<'a, R>,
+pub struct MapAccess<'a, R: Read, B> {
+    reader: ChildReader<'a, R, B>,
     attributes: Peekable<std::vec::IntoIter<Attribute>>,
     fields: &'static [&'static str],
+    self_describing: bool,
+    /// Index of the field that the last child element went to, to check their order
+    last_field: Option<usize>,
+    /// Whether the visitor reads keys as identifiers, as structs with flattened fields do
+    identifier_keys: bool,
 }
 
-impl<'a, R: Read> MapAccess<'a, R> {
-    pub fn new_map(reader: ChildReader<'a, R>) -> Self {
+impl<'a, R: Read, B> MapAccess<'a, R, B> {
+    pub fn new_map(reader: ChildReader<'a, R, B>, attributes: Vec<Attribute>) -> Self {
         Self {
             reader,
-            attributes: vec![].into_iter().peekable(),
+            attributes: attributes.into_iter().peekable(),
             fields: &[],
+            self_describing: false,
+            last_field: None,
+            identifier_keys: false,
+        }
+    }
+
+    /// A map whose keys are discovered from the document, grouping repeated sibling elements
+    /// into sequences.
+    pub fn new_any(reader: ChildReader<'a, R, B>, attributes: Vec<Attribute>) -> Self {
+        Self {
+            reader,
+            attributes: attributes.into_iter().peekable(),
+            fields: &[],
+            self_describing: true,
+            last_field: None,
+            identifier_keys: false,
         }
     }
 
     pub fn new_struct(
-        reader: ChildReader<'a, R>,
+        reader: ChildReader<'a, R, B>,
         attributes: Vec<Attribute>,
         fields: &'static [&'static str],
     ) -> Self {
@@ -35,15 +65,117 @@ impl<'a, R: Read> MapAccess<'a, R> {
             reader,
             attributes: attributes.into_iter().peekable(),
             fields,
+            self_describing: false,
+            last_field: None,
+            identifier_keys: false,
         }
     }
+}
+
+/// Deserializes the key of a map entry, noting whether the visitor reads it as an identifier
+struct KeyDeserializer<'a> {
+    key: &'a str,
+    identifier: &'a mut bool,
+}
+
+impl<'de> serde::Deserializer<'de> for KeyDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.key.into_deserializer().deserialize_any(visitor)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        *self.identifier = true;
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.key
+            .into_deserializer()
+            .deserialize_enum(name, variants, visitor)
+    }
 
-    fn is_content(&self, element_name: &str) -> bool {
-        !self.fields.contains(&element_name) && self.fields.contains(&CONTENT)
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct ignored_any
+    }
+}
+
+/// The field that an attribute goes to, or else the name under which maps see it
+fn attribute_key(fields: &[&str], config: &SerdeXml, attribute: &Attribute) -> String {
+    let name = attribute.resolved_name();
+    fields
+        .iter()
+        .find(|field| {
+            config
+                .markers
+                .accepted_attribute_name(field)
+                .is_some_and(|field| name.matches(field, config))
+        })
+        .map(|field| field.to_string())
+        .unwrap_or_else(|| {
+            format!(
+                "{}{}",
+                config.markers.attribute,
+                name.canonical(&config.namespaces)
+            )
+        })
+}
+
+/// The field that an element goes to, or else the name under which maps see it, unless it goes
+/// to the content field
+fn element_key(fields: &[&str], config: &SerdeXml, element: &Element) -> Option<String> {
+    let name = element.resolved_name();
+    match fields
+        .iter()
+        .find(|field| config.markers.is_element(field) && name.matches(field, config))
+    {
+        Some(field) => Some(field.to_string()),
+        None if config.markers.content_field(fields).is_some() => None,
+        None => Some(name.canonical(&config.namespaces)),
     }
 }
 
-impl<'de, R: Read> serde::de::MapAccess<'de> for MapAccess<'_, R> {
+/// Check that a child element going to the field `key` does not come after an element that goes
+/// to a later field, and remember its field
+fn check_order(
+    fields: &[&str],
+    last_field: &mut Option<usize>,
+    element_name: &str,
+    key: &str,
+) -> Result<()> {
+    let Some(index) = fields.iter().position(|field| *field == key) else {
+        return Ok(());
+    };
+    match *last_field {
+        Some(last) if index < last => Err(Error::UnexpectedOrder {
+            found: element_name.to_string(),
+            before: fields[last].to_string(),
+        }),
+        _ => {
+            *last_field = Some(index);
+            Ok(())
+        }
+    }
+}
+
+impl<'de, R: Read, B: Input<'de, R>> serde::de::MapAccess<'de> for MapAccess<'_, R, B> {
     type Error = Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
@@ -51,29 +183,79 @@ impl<'de, R: Read> serde::de::MapAccess<'de> for MapAccess<'_, R> {
         K: serde::de::DeserializeSeed<'de>,
     {
         trace!("next map/struct key");
+        let config = self.reader.config;
+        if (config.denies_unknown() || self.reader.collector.is_enabled())
+            && !self.fields.is_empty()
+        {
+            // xsi attributes instruct processors rather than hold content, so structs skip them
+            // unless a field asks for them, rather than fail or report them
+            while self
+                .attributes
+                .next_if(|attr| {
+                    attr.is_xsi()
+                        && !self
+                            .fields
+                            .contains(&attribute_key(self.fields, config, attr).as_str())
+                })
+                .is_some()
+            {}
+        }
         if let Some(attr) = self.attributes.peek() {
             trace!("attribute {}", attr.qname());
-            seed.deserialize(format!("@{}", attr.qname()).into_deserializer())
-                .map(Some)
+            let key = attribute_key(self.fields, self.reader.config, attr);
+            seed.deserialize(KeyDeserializer {
+                key: &key,
+                identifier: &mut self.identifier_keys,
+            })
+            .map(Some)
+            .map_err(|error: Error| error.within(Step::Attribute(attr.qname())))
         } else {
+            let config = self.reader.config;
+            let position = self.reader.position()?;
             match self.reader.peek()? {
                 Event::StartElement(element) => {
                     let element_name = element.qname();
-                    if self.is_content(&element_name) {
-                        trace!("#content");
-                        seed.deserialize(CONTENT.into_deserializer()).map(Some)
-                    } else {
+                    if let Some(key) = element_key(self.fields, config, element) {
                         trace!("element '{}'", element_name);
-                        seed.deserialize(element_name.as_str().into_deserializer())
+                        if config.verifies_order() {
+                            check_order(self.fields, &mut self.last_field, &element_name, &key)
+                        } else {
+                            Ok(())
+                        }
+                        .and_then(|()| {
+                            seed.deserialize(KeyDeserializer {
+                                key: &key,
+                                identifier: &mut self.identifier_keys,
+                            })
                             .map(Some)
+                        })
+                    } else {
+                        let content = config
+                            .markers
+                            .content_field(self.fields)
+                            .unwrap_or_default();
+                        trace!("{}", content);
+                        seed.deserialize(KeyDeserializer {
+                            key: content,
+                            identifier: &mut self.identifier_keys,
+                        })
+                        .map(Some)
                     }
+                    .map_err(|error: Error| error.within(Step::element(element_name)))
                 }
                 Event::Text(_) => {
-                    trace!("{}", TEXT);
-                    seed.deserialize(TEXT.into_deserializer()).map(Some)
+                    let text = config.markers.text_field(self.fields);
+                    trace!("{}", text);
+                    seed.deserialize(KeyDeserializer {
+                        key: text,
+                        identifier: &mut self.identifier_keys,
+                    })
+                    .map(Some)
+                    .map_err(|error: Error| error.within(Step::Text))
                 }
                 _ => Ok(None),
             }
+            .map_err(|error| error.at(position))
         }
     }
 
@@ -81,29 +263,94 @@ impl<'de, R: Read> serde::de::MapAccess<'de> for MapAccess<'_, R> {
     where
         V: serde::de::DeserializeSeed<'de>,
     {
+        // The values of keys that a struct does not take as its own fields are buffered
+        let buffered = self.identifier_keys && self.fields.is_empty();
         if let Some(attr) = self.attributes.next() {
-            seed.deserialize(PlainTextDeserializer::new(&attr.value))
+            let position = self.reader.collector.position();
+            let mark = self
+                .reader
+                .collector
+                .enter(|| Step::Attribute(attr.qname()), position);
+            let value = seed
+                .deserialize(
+                    PlainTextDeserializer::new(&attr.value, self.reader.config)
+                        .borrowing(self.reader.lend(&attr.span))
+                        .reporting(self.reader.collector, IgnoredKind::Attribute)
+                        .buffered(buffered),
+                )
+                .map_err(|error| error.within(Step::Attribute(attr.qname())));
+            self.reader.collector.leave(mark);
+            value
         } else {
+            let config = self.reader.config;
+            let position = self.reader.position()?;
             match self.reader.peek()? {
-                Event::StartElement(element) => {
-                    let element_name = element.qname();
-                    if self.is_content(&element_name) {
-                        seed.deserialize(ChildDeserializer::new(self.reader.child()))
+                // The element is stepped into by whatever deserializes it, with its index in a
+                // sequence
+                Event::StartElement(element) => match element_key(self.fields, config, element) {
+                    None => seed.deserialize(ChildDeserializer::new(self.reader.child())),
+                    Some(key) => {
+                        let sequence =
+                            self.self_describing && self.reader.has_sibling_named(&key)?;
+                        let ordered = config.verifies_order() && !self.fields.is_empty();
+                        if sequence {
+                            let mut reader = self.reader.child();
+                            reader.overlapping_sequences = true;
+                            seed.deserialize(SeqAccessDeserializer::new(SeqAccess::new(
+                                reader,
+                                Some(key),
+                            )))
+                        } else {
+                            let mut reader = self.reader.child();
+                            // Gathering later elements of a sequence would hide their order
+                            reader.overlapping_sequences &= !ordered;
+                            seed.deserialize(
+                                ChildDeserializer::new_with_element_name(reader, key)
+                                    .buffered(buffered),
+                            )
+                        }
+                    }
+                },
+                Event::Text(_) if self.self_describing => {
+                    // Text around child elements is grouped like repeated elements, in order
+                    let mut texts = vec![self.reader.text()?];
+                    texts.extend(self.reader.take_sibling_texts()?);
+                    let mark = self.reader.collector.enter(|| Step::Text, position);
+                    let value = if texts.len() == 1 {
+                        let text = texts.remove(0);
+                        seed.deserialize(
+                            PlainTextDeserializer::new(&text.value, self.reader.config)
+                                .borrowing(self.reader.lend(&text.span)),
+                        )
                     } else {
-                        seed.deserialize(ChildDeserializer::new_with_element_name(
-                            self.reader.child(),
-                            element_name,
+                        seed.deserialize(SeqDeserializer::new(
+                            texts.into_iter().map(|text| text.value),
                         ))
                     }
+                    .map_err(|error| error.within(Step::Text));
+                    self.reader.collector.leave(mark);
+                    value
                 }
                 Event::Text(_) => {
-                    seed.deserialize(PlainTextDeserializer::new(&self.reader.chars()?))
+                    let text = self.reader.text()?;
+                    let mark = self.reader.collector.enter(|| Step::Text, position);
+                    let value = seed
+                        .deserialize(
+                            PlainTextDeserializer::new(&text.value, self.reader.config)
+                                .borrowing(self.reader.lend(&text.span))
+                                .reporting(self.reader.collector, IgnoredKind::Text)
+                                .buffered(buffered),
+                        )
+                        .map_err(|error| error.within(Step::Text));
+                    self.reader.collector.leave(mark);
+                    value
                 }
                 event => Err(Error::Unexpected {
                     expected: "start of element or text",
                     but_got: event.to_string(),
                 }),
             }
+            .map_err(|error| error.at(position))
         }
     }
 }
@@ -1,20 +1,36 @@
+#[cfg(feature = "async")]
+mod asynchronous;
 mod child;
+mod input;
 mod map;
+mod name;
 mod plain;
 mod reader;
+mod replay;
+mod report;
 mod seq;
+mod stream;
 mod var;
 
+#[cfg(feature = "async")]
+pub use self::asynchronous::{from_async_reader, AsyncStreamDeserializer};
+pub use self::{
+    input::{BorrowedInput, Input, OwnedInput},
+    replay::Replay,
+    report::{Ignored, IgnoredKind},
+    stream::StreamDeserializer,
+};
+
 use self::{
     child::ChildDeserializer,
     map::MapAccess,
-    plain::PlainTextDeserializer,
-    reader::{Event, RootReader},
+    reader::{Element, Event, RootReader},
+    seq::{SeqAccess, TupleAccess},
     var::EnumAccess,
 };
 use crate::{
-    config::SerdeXml,
-    error::{Error, Result},
+    config::{SerdeXml, ROOT_ITEM},
+    error::{Error, Result, Step},
 };
 use log::trace;
 use reader::Reader;
@@ -24,6 +40,9 @@ use xml::EventReader;
 
 /// A convenience method for deserialize some object from a string.
 ///
+/// Nothing is borrowed from the string, so `T` need not outlive it. Use [from_slice] to borrow
+/// text and attribute values from the input.
+///
 /// ```rust
 /// # use serde::{Deserialize, Serialize};
 /// # use serde_xml_rs::from_str;
@@ -42,6 +61,31 @@ pub fn from_str<'de, T: Deserialize<'de>>(s: &str) -> Result<T> {
     from_reader(s.as_bytes())
 }
 
+/// A convenience method for deserialize some object from bytes held in memory.
+///
+/// Text and attribute values that appear verbatim in the input can be borrowed by `&str` or
+/// `Cow<str>` fields. Values with entities or character references, or from
+/// documents that are not encoded in UTF-8, are owned.
+///
+/// ```rust
+/// # use serde::Deserialize;
+/// # use serde_xml_rs::from_slice;
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Item<'a> {
+///     name: &'a str,
+///     #[serde(rename = "@source")]
+///     source: &'a str,
+/// }
+/// # fn main() {
+/// let s = br##"<item source="world.rs"><name>hello</name></item>"##;
+/// let item: Item = from_slice(s).unwrap();
+/// assert_eq!(item, Item { name: "hello", source: "world.rs" });
+/// # }
+/// ```
+pub fn from_slice<'de, T: Deserialize<'de>>(input: &'de [u8]) -> Result<T> {
+    Deserializer::from_config_slice(SerdeXml::default(), input).deserialize_document()
+}
+
 /// A convenience method for deserialize some object from a reader.
 ///
 /// ```rust
@@ -59,17 +103,20 @@ pub fn from_str<'de, T: Deserialize<'de>>(s: &str) -> Result<T> {
 /// # }
 /// ```
 pub fn from_reader<'de, T: Deserialize<'de>, R: Read>(reader: R) -> Result<T> {
-    T::deserialize(&mut Deserializer::from_config(SerdeXml::default(), reader))
+    Deserializer::from_config(SerdeXml::default(), reader).deserialize_document()
 }
 
-pub struct Deserializer<R: Read> {
-    reader: RootReader<R>,
+pub struct Deserializer<R: Read, B = OwnedInput> {
+    reader: RootReader<R, B>,
+    /// Name that the root element must have, given at runtime
+    root_element_name: Option<String>,
 }
 
 impl<R: Read> Deserializer<R> {
     pub fn new(reader: EventReader<R>) -> Self {
         Self {
-            reader: RootReader::new(reader, false),
+            reader: RootReader::new(reader, OwnedInput, SerdeXml::default()),
+            root_element_name: None,
         }
     }
 
@@ -80,9 +127,199 @@ impl<R: Read> Deserializer<R> {
     pub fn from_config(config: SerdeXml, source: R) -> Self {
         Self {
             reader: RootReader::new(
-                config.parser.create_reader(source),
-                config.overlapping_sequences,
+                config.parser.clone().create_reader(source),
+                OwnedInput,
+                config,
+            ),
+            root_element_name: None,
+        }
+    }
+}
+
+impl<'de> Deserializer<&'de [u8], BorrowedInput<'de>> {
+    pub fn from_slice(input: &'de [u8]) -> Self {
+        Self::from_config_slice(SerdeXml::default(), input)
+    }
+
+    pub fn from_config_slice(config: SerdeXml, input: &'de [u8]) -> Self {
+        Self {
+            reader: RootReader::new(
+                config.parser.clone().create_reader(input),
+                BorrowedInput::new(input),
+                config,
             ),
+            root_element_name: None,
+        }
+    }
+}
+
+impl<'de, R: Read, B: Input<'de, R>> Deserializer<R, B> {
+    /// Iterate over the consecutive root elements of the input, deserializing each as a `T`.
+    /// See [StreamDeserializer].
+    #[allow(clippy::should_implement_trait)]
+    pub fn into_iter<T: Deserialize<'de>>(self) -> StreamDeserializer<'de, R, B, T> {
+        StreamDeserializer::new(self)
+    }
+
+    /// Iterate over the elements selected by a path of element names separated by slashes,
+    /// such as `rss/channel/item`, deserializing each as a `T`. See [StreamDeserializer].
+    pub fn into_iter_path<T: Deserialize<'de>>(
+        self,
+        path: &str,
+    ) -> StreamDeserializer<'de, R, B, T> {
+        StreamDeserializer::new_with_path(self, path)
+    }
+
+    /// Require the root element to have a name chosen at runtime, which can be a qualified name
+    /// or a name in Clark notation. Otherwise, deserialization fails with
+    /// [Error::UnexpectedRoot].
+    pub fn with_root_element_name<S: ToString>(mut self, name: S) -> Self {
+        self.root_element_name = Some(name.to_string());
+        self
+    }
+
+    /// Check that the input ends after the values deserialized so far, apart from whitespace,
+    /// comments and processing instructions. Otherwise, fails at the position of the trailing
+    /// content, such as a second root element.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::Deserializer;
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    /// # fn main() {
+    /// let mut deserializer = Deserializer::from_slice(b"<order><id>1</id></order><order />");
+    /// let order = Order::deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(order, Order { id: 1 });
+    /// assert!(deserializer.end().is_err());
+    /// # }
+    /// ```
+    pub fn end(&mut self) -> Result<()> {
+        let position = self.reader.position()?;
+        match self.reader.peek()? {
+            Event::Eof => Ok(()),
+            event => Err(Error::Unexpected {
+                expected: "end of input",
+                but_got: event.to_string(),
+            }
+            .at(position)),
+        }
+    }
+
+    /// Record the content that no field deserializes, such as unknown elements, attributes and
+    /// text, instead of skipping it silently. Take the records with [Deserializer::take_ignored].
+    ///
+    /// Content that serde buffers, for flattened fields or untagged enums, is not looked into.
+    pub fn with_report(mut self) -> Self {
+        self.reader.collector.enable();
+        self
+    }
+
+    /// The content skipped since the last call, in document order, if enabled with
+    /// [Deserializer::with_report].
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_xml_rs::{Deserializer, IgnoredKind};
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    /// # fn main() {
+    /// let mut deserializer =
+    ///     Deserializer::from_slice(br#"<order status="open"><id>1</id><note /></order>"#)
+    ///         .with_report();
+    /// let order = Order::deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(order, Order { id: 1 });
+    /// let ignored = deserializer.take_ignored();
+    /// assert_eq!(ignored[0].kind, IgnoredKind::Attribute);
+    /// assert_eq!(ignored[0].path.to_string(), "/order/@status");
+    /// assert_eq!(ignored[1].kind, IgnoredKind::Element);
+    /// assert_eq!(ignored[1].path.to_string(), "/order/note");
+    /// # }
+    /// ```
+    pub fn take_ignored(&mut self) -> Vec<Ignored> {
+        self.reader.collector.take()
+    }
+
+    /// Deserialize a document holding a single `T`, checking the end of the input if configured
+    pub(crate) fn deserialize_document<T: Deserialize<'de>>(&mut self) -> Result<T> {
+        let value = T::deserialize(&mut *self)?;
+        if self.reader.config.verifies_end() {
+            self.end()?;
+        }
+        Ok(value)
+    }
+
+    /// Run `f`, attaching the position and name of the root element to any error it raises
+    fn located<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.located_as(None, f)
+    }
+
+    /// Same as `located`, for a value of a type with a name in serde that the root element may
+    /// have to match
+    fn located_as<T>(
+        &mut self,
+        type_name: Option<&str>,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let position = self.reader.position()?;
+        let element = match self.reader.peek()? {
+            Event::StartElement(element) => Some(element.clone()),
+            _ => None,
+        };
+        let mark = element.as_ref().map(|element| {
+            self.reader
+                .collector
+                .enter(|| Step::element(element.qname()), position)
+        });
+        let result = match &element {
+            Some(element) => self
+                .check_root_name(element, type_name)
+                .and_then(|()| f(self)),
+            None => f(self),
+        };
+        if let Some(mark) = mark {
+            self.reader.collector.leave(mark);
+        }
+        result.map_err(|error| match &element {
+            Some(element) => error.at(position).within(Step::element(element.qname())),
+            None => error.at(position),
+        })
+    }
+
+    /// Check that the root element has the name given at runtime, if any, or else the name of
+    /// the type or the configured root element name if verification is enabled
+    fn check_root_name(&self, element: &Element, type_name: Option<&str>) -> Result<()> {
+        let config = &self.reader.config;
+        let expected = match (&self.root_element_name, type_name) {
+            (Some(expected), _) => expected.as_str(),
+            (None, Some(type_name)) if config.verifies_root_name() => type_name,
+            (None, None) if config.verifies_root_name() => match &config.root_element_name {
+                Some(expected) => expected.as_str(),
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        if element.resolved_name().matches(expected, config) {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedRoot {
+                expected: expected.to_string(),
+                found: element.qname(),
+            })
+        }
+    }
+
+    fn peek_element_name(&mut self) -> Result<String> {
+        match self.reader.peek()? {
+            Event::StartElement(element) => Ok(element.qname()),
+            event => Err(Error::Unexpected {
+                expected: "start of element",
+                but_got: event.to_string(),
+            }),
         }
     }
 }
@@ -90,25 +327,44 @@ impl<R: Read> Deserializer<R> {
 macro_rules! deserialize_type {
     ($deserialize:ident => $visit:ident) => {
         fn $deserialize<V: ::serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-            self.reader.start_element()?;
-            let value = self.reader.chars()?.parse()?;
-            self.reader.end_element()?;
-            visitor.$visit(value)
+            self.located(|this| {
+                this.reader.start_element_without_attributes()?;
+                let value = this.reader.chars()?.parse()?;
+                this.reader.end_element()?;
+                visitor.$visit(value)
+            })
         }
     };
 }
 
-impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
+impl<'de, R: Read, B: Input<'de, R>> serde::Deserializer<'de> for &mut Deserializer<R, B> {
     type Error = Error;
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::Unsupported("deserialize any"))
+        trace!("Root any");
+        self.located(|this| {
+            let element_name = this.peek_element_name()?;
+            ChildDeserializer::new_with_element_name(this.reader.child(), element_name)
+                .without_step()
+                .deserialize_any(visitor)
+        })
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.located(|this| {
+            let element_name = this.peek_element_name()?;
+            ChildDeserializer::new_with_element_name(this.reader.child(), element_name)
+                .without_step()
+                .deserialize_bool(visitor)
+        })
     }
 
-    deserialize_type!(deserialize_bool => visit_bool);
     deserialize_type!(deserialize_i8 => visit_i8);
     deserialize_type!(deserialize_i16 => visit_i16);
     deserialize_type!(deserialize_i32 => visit_i32);
@@ -138,29 +394,31 @@ impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: Visitor<'de>,
     {
-        self.reader.start_element()?;
-        let text = if matches!(self.reader.peek()?, Event::Text(_)) {
-            self.reader.chars()?
-        } else {
-            "".to_string()
-        };
-        let value = visitor.visit_string::<Self::Error>(text)?;
-        self.reader.end_element()?;
-        Ok(value)
+        self.located(|this| {
+            let element_name = this.peek_element_name()?;
+            ChildDeserializer::new_with_element_name(this.reader.child(), element_name)
+                .without_step()
+                .deserialize_string(visitor)
+        })
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::Unsupported("bytes"))
+        self.located(|this| {
+            let element_name = this.peek_element_name()?;
+            ChildDeserializer::new_with_element_name(this.reader.child(), element_name)
+                .without_step()
+                .deserialize_bytes(visitor)
+        })
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::Unsupported("byte buf"))
+        self.deserialize_bytes(visitor)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
@@ -169,6 +427,10 @@ impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
     {
         match self.reader.peek()? {
             Event::EndElement => visitor.visit_none(),
+            Event::StartElement(element) if element.is_nil() => {
+                self.located(|this| this.reader.child().ignore())?;
+                visitor.visit_none()
+            }
             _ => visitor.visit_some(self),
         }
     }
@@ -185,10 +447,12 @@ impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
         V: Visitor<'de>,
     {
         trace!("Root unit struct '{name}'");
-        self.reader.start_element()?;
-        let value = visitor.visit_unit::<Self::Error>()?;
-        self.reader.end_element()?;
-        Ok(value)
+        self.located_as(Some(name), |this| {
+            this.reader.start_element_without_attributes()?;
+            let value = visitor.visit_unit::<Error>()?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
@@ -196,17 +460,39 @@ impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
         V: Visitor<'de>,
     {
         trace!("Root newtype struct '{name}'");
-        self.reader.start_element()?;
-        let value = visitor.visit_newtype_struct(ChildDeserializer::new(self.reader.child()))?;
-        self.reader.end_element()?;
-        Ok(value)
+        if name == replay::REPLAY {
+            return self.located(|this| {
+                let element_name = this.peek_element_name()?;
+                let events = this.reader.child().capture_element()?;
+                replay::visit_element(visitor, &this.reader.config, element_name, events)
+            });
+        }
+        self.located_as(Some(name), |this| {
+            this.reader.start_element_without_attributes()?;
+            let value =
+                visitor.visit_newtype_struct(ChildDeserializer::new(this.reader.child()))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
-    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::Unsupported("sequence in document root"))
+        trace!("Root sequence");
+        if self.root_element_name.is_none() && self.reader.config.root_element_name.is_none() {
+            return Err(Error::Unsupported("sequence in document root"));
+        }
+        self.located(|this| {
+            this.reader.start_element_without_attributes()?;
+            let value = visitor.visit_seq(SeqAccess::new(
+                this.reader.child(),
+                Some(ROOT_ITEM.to_string()),
+            ))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
@@ -214,24 +500,30 @@ impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
         V: Visitor<'de>,
     {
         trace!("Root tuple");
-        self.reader.start_element()?;
-        let text = self.reader.chars()?;
-        let value = visitor.visit_seq(PlainTextDeserializer::new(&text))?;
-        self.reader.end_element()?;
-        Ok(value)
+        self.located(|this| {
+            this.reader.start_element_without_attributes()?;
+            let value = visitor.visit_seq(TupleAccess::new(this.reader.child()))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_tuple_struct<V>(
         self,
         name: &'static str,
-        len: usize,
+        _len: usize,
         visitor: V,
     ) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
         trace!("Root tuple struct '{name}'");
-        self.deserialize_tuple(len, visitor)
+        self.located_as(Some(name), |this| {
+            this.reader.start_element_without_attributes()?;
+            let value = visitor.visit_seq(TupleAccess::new(this.reader.child()))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
@@ -239,10 +531,13 @@ impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
         V: Visitor<'de>,
     {
         trace!("Root map");
-        self.reader.start_element()?;
-        let value = visitor.visit_map(MapAccess::new_map(self.reader.child()))?;
-        self.reader.end_element()?;
-        Ok(value)
+        self.located(|this| {
+            let element = this.reader.start_element()?;
+            let value =
+                visitor.visit_map(MapAccess::new_map(this.reader.child(), element.attributes))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_struct<V>(
@@ -255,30 +550,34 @@ impl<'de, R: Read> serde::Deserializer<'de> for &mut Deserializer<R> {
         V: Visitor<'de>,
     {
         trace!("Root struct '{name}'");
-        let element = self.reader.start_element()?;
-        let value = visitor.visit_map(MapAccess::new_struct(
-            self.reader.child(),
-            element.attributes,
-            fields,
-        ))?;
-        self.reader.end_element()?;
-        Ok(value)
+        self.located_as(Some(name), |this| {
+            let element = this.reader.start_element()?;
+            let value = visitor.visit_map(MapAccess::new_struct(
+                this.reader.child(),
+                element.attributes,
+                fields,
+            ))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_enum<V>(
         self,
         name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
         trace!("Root enum '{name}'");
-        self.reader.start_element()?;
-        let value = visitor.visit_enum(EnumAccess::new(self.reader.child()))?;
-        self.reader.end_element()?;
-        Ok(value)
+        self.located_as(Some(name), |this| {
+            this.reader.start_element_without_attributes()?;
+            let value = visitor.visit_enum(EnumAccess::new(this.reader.child(), variants))?;
+            this.reader.end_element()?;
+            Ok(value)
+        })
     }
 
     fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value>
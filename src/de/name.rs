@@ -0,0 +1,84 @@
+use crate::config::{NameMatching, Namespaces, SerdeXml};
+use xml::namespace::NS_NO_PREFIX;
+
+/// The name of an element or attribute as it appears in the document, with the namespace its
+/// prefix resolves to.
+pub struct Name<'a> {
+    pub prefix: Option<&'a str>,
+    pub namespace: Option<&'a str>,
+    pub local: &'a str,
+}
+
+impl Name<'_> {
+    /// Whether this name matches the name of a field or variant, which can be
+    /// - `{uri}local` in Clark notation, matching by namespace URI
+    /// - `prefix:local`, matching by the namespace URI configured for the prefix in `SerdeXml`, or
+    ///   literally if the prefix is not configured
+    /// - `local`, matching unprefixed names, and names in the default namespace configured in
+    ///   `SerdeXml`
+    ///
+    /// Local names are compared according to the configured [NameMatching], which may also
+    /// ignore prefixes and namespaces altogether.
+    pub fn matches(&self, name: &str, config: &SerdeXml) -> bool {
+        let namespaces = &config.namespaces;
+        let same_local = |local: &str| match config.name_matching {
+            NameMatching::Exact | NameMatching::LocalName => self.local == local,
+            NameMatching::IgnoreAsciiCase => self.local.eq_ignore_ascii_case(local),
+        };
+        if let Some(clark) = name.strip_prefix('{') {
+            return match clark.split_once('}') {
+                Some((_, local)) if config.name_matching == NameMatching::LocalName => {
+                    same_local(local)
+                }
+                Some((uri, local)) => self.namespace == Some(uri) && same_local(local),
+                None => false,
+            };
+        }
+        match name.split_once(':') {
+            Some((_, local)) if config.name_matching == NameMatching::LocalName => {
+                same_local(local)
+            }
+            Some((prefix, local)) => {
+                same_local(local)
+                    && match namespaces.get(prefix) {
+                        Some(uri) => self.namespace == Some(uri.as_str()),
+                        None => self.prefix == Some(prefix),
+                    }
+            }
+            None if config.name_matching == NameMatching::LocalName => same_local(name),
+            None => {
+                same_local(name)
+                    && (self.prefix.is_none()
+                        || self.namespace.is_some()
+                            && self.namespace == namespaces.get(NS_NO_PREFIX).map(String::as_str))
+            }
+        }
+    }
+
+    /// The name under which maps and self-describing types see this name: with the prefix
+    /// configured in `SerdeXml` for its namespace, in Clark notation if its own prefix is
+    /// configured for another namespace, or else as it appears in the document
+    pub fn canonical(&self, namespaces: &Namespaces) -> String {
+        let Some(uri) = self.namespace else {
+            return self.qname();
+        };
+        match namespaces.prefix_for(uri) {
+            Some(NS_NO_PREFIX) => self.local.to_string(),
+            Some(prefix) => format!("{prefix}:{}", self.local),
+            None if namespaces
+                .get(self.prefix.unwrap_or(NS_NO_PREFIX))
+                .is_some() =>
+            {
+                format!("{{{uri}}}{}", self.local)
+            }
+            None => self.qname(),
+        }
+    }
+
+    pub fn qname(&self) -> String {
+        match self.prefix {
+            Some(prefix) => format!("{prefix}:{}", self.local),
+            None => self.local.to_string(),
+        }
+    }
+}
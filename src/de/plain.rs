@@ -1,15 +1,51 @@
-use super::reader::parse_xml_bool;
-use crate::error::{Error, Result};
+use super::{
+    replay,
+    report::{Collector, IgnoredKind},
+};
+use crate::{
+    config::{BinaryEncoding, SerdeXml},
+    error::{Error, Result},
+};
 use log::trace;
 use serde::de::{value::StrDeserializer, IntoDeserializer, Visitor};
 
-pub struct PlainTextDeserializer<'a> {
+pub struct PlainTextDeserializer<'a, 'de> {
     text: &'a str,
+    borrowed: Option<&'de str>,
+    config: &'a SerdeXml,
+    /// Where to record the text if it is skipped, and as what kind of content
+    report: Option<(&'a mut Collector, IgnoredKind)>,
+    /// Whether the text is recorded as unchecked if it is deserialized as any type
+    buffered: bool,
 }
 
-impl<'a> PlainTextDeserializer<'a> {
-    pub fn new(text: &'a str) -> Self {
-        Self { text }
+impl<'a, 'de> PlainTextDeserializer<'a, 'de> {
+    pub fn new(text: &'a str, config: &'a SerdeXml) -> Self {
+        Self {
+            text,
+            borrowed: None,
+            config,
+            report: None,
+            buffered: false,
+        }
+    }
+
+    /// Use the same text borrowed from the input, if available
+    pub fn borrowing(mut self, borrowed: Option<&'de str>) -> Self {
+        self.borrowed = borrowed;
+        self
+    }
+
+    /// Record the text at the current path of the collector if it is skipped
+    pub fn reporting(mut self, collector: &'a mut Collector, kind: IgnoredKind) -> Self {
+        self.report = Some((collector, kind));
+        self
+    }
+
+    /// Record the text as unchecked if it is buffered
+    pub fn buffered(mut self, buffered: bool) -> Self {
+        self.buffered = buffered;
+        self
     }
 }
 
@@ -22,13 +58,17 @@ macro_rules! deserialize_type {
     };
 }
 
-impl<'de> serde::de::Deserializer<'de> for PlainTextDeserializer<'_> {
+impl<'de> serde::de::Deserializer<'de> for PlainTextDeserializer<'_, 'de> {
     type Error = Error;
 
-    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        if let (true, Some((collector, _))) = (self.buffered, &mut self.report) {
+            let position = collector.position();
+            collector.record(IgnoredKind::Unchecked, None, position);
+        }
         self.deserialize_string(visitor)
     }
 
@@ -36,7 +76,7 @@ impl<'de> serde::de::Deserializer<'de> for PlainTextDeserializer<'_> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_bool(parse_xml_bool(self.text)?)
+        visitor.visit_bool(self.config.booleans.parse(self.text)?)
     }
 
     deserialize_type!(deserialize_i8 => visit_i8);
@@ -68,21 +108,29 @@ impl<'de> serde::de::Deserializer<'de> for PlainTextDeserializer<'_> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_string(self.text.to_string())
+        match self.borrowed {
+            Some(borrowed) => visitor.visit_borrowed_str(borrowed),
+            None => visitor.visit_string(self.text.to_string()),
+        }
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(Error::Unsupported("bytes"))
+        match (self.config.binary_encoding, self.borrowed) {
+            (BinaryEncoding::Utf8, Some(borrowed)) => {
+                visitor.visit_borrowed_bytes(borrowed.as_bytes())
+            }
+            (encoding, _) => visitor.visit_byte_buf(encoding.decode(self.text)?),
+        }
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_byte_buf(self.text.to_string().into_bytes())
+        self.deserialize_bytes(visitor)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
@@ -106,10 +154,13 @@ impl<'de> serde::de::Deserializer<'de> for PlainTextDeserializer<'_> {
         visitor.visit_unit()
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        if name == replay::REPLAY {
+            return replay::visit_text(visitor, self.config, self.text);
+        }
         visitor.visit_newtype_struct(self)
     }
 
@@ -182,11 +233,18 @@ impl<'de> serde::de::Deserializer<'de> for PlainTextDeserializer<'_> {
         V: Visitor<'de>,
     {
         trace!("ignoring {}", self.text);
+        if self.config.denies_unknown() {
+            return Err(Error::UnknownContent(format!("value '{}'", self.text)));
+        }
+        if let Some((collector, kind)) = self.report {
+            let position = collector.position();
+            collector.record(kind, None, position);
+        }
         visitor.visit_unit()
     }
 }
 
-impl<'de> serde::de::EnumAccess<'de> for PlainTextDeserializer<'_> {
+impl<'de> serde::de::EnumAccess<'de> for PlainTextDeserializer<'_, 'de> {
     type Error = Error;
     type Variant = Self;
 
@@ -201,7 +259,7 @@ impl<'de> serde::de::EnumAccess<'de> for PlainTextDeserializer<'_> {
     }
 }
 
-impl<'de> serde::de::VariantAccess<'de> for PlainTextDeserializer<'_> {
+impl<'de> serde::de::VariantAccess<'de> for PlainTextDeserializer<'_, 'de> {
     type Error = Error;
 
     fn unit_variant(self) -> Result<()> {
@@ -230,7 +288,7 @@ impl<'de> serde::de::VariantAccess<'de> for PlainTextDeserializer<'_> {
     }
 }
 
-impl<'de> serde::de::SeqAccess<'de> for PlainTextDeserializer<'_> {
+impl<'de> serde::de::SeqAccess<'de> for PlainTextDeserializer<'_, 'de> {
     type Error = Error;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
@@ -239,13 +297,18 @@ impl<'de> serde::de::SeqAccess<'de> for PlainTextDeserializer<'_> {
     {
         if self.text.is_empty() {
             Ok(None)
-        } else if let Some((next_element, remaining_elements)) = self.text.split_once(' ') {
-            self.text = remaining_elements;
-            let value = seed.deserialize(PlainTextDeserializer::new(next_element))?;
-            Ok(Some(value))
         } else {
-            let value = seed.deserialize(PlainTextDeserializer::new(self.text))?;
-            self.text = "";
+            let (next_element, remaining_elements) =
+                self.text.split_once(' ').unwrap_or((self.text, ""));
+            let borrowed = self.borrowed.map(|borrowed| {
+                let (next, remaining) = borrowed.split_at(next_element.len());
+                self.borrowed = Some(remaining.strip_prefix(' ').unwrap_or(remaining));
+                next
+            });
+            self.text = remaining_elements;
+            let value = seed.deserialize(
+                PlainTextDeserializer::new(next_element, self.config).borrowing(borrowed),
+            )?;
             Ok(Some(value))
         }
     }
@@ -1,19 +1,44 @@
-use crate::error::{Error, Result};
+use super::{
+    input::{Input, OwnedInput},
+    name::Name,
+    report::{Collector, IgnoredKind},
+};
+use crate::{
+    config::{SerdeXml, XSI_NAMESPACE},
+    error::{Error, Position, Result, Step},
+};
 use log::trace;
-use std::{collections::VecDeque, io::Read};
-use xml::{attribute::OwnedAttribute, name::OwnedName, reader::XmlEvent, EventReader};
+use std::{collections::VecDeque, io::Read, ops::Range};
+use xml::{
+    attribute::OwnedAttribute, common::Position as _, name::OwnedName, reader::XmlEvent,
+    EventReader,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Event {
     StartElement(Element),
-    Text(String),
+    Text(Text),
     EndElement,
     Eof,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Text {
+    pub value: String,
+    /// Where the text appears verbatim in the input, if it can be borrowed from it
+    pub span: Option<Range<usize>>,
+}
+
+impl Text {
+    pub fn new(value: String) -> Self {
+        Self { value, span: None }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Element {
     pub prefix: Option<String>,
+    pub namespace: Option<String>,
     pub name: String,
     pub attributes: Vec<Attribute>,
 }
@@ -22,59 +47,93 @@ impl Element {
     pub fn from(name: OwnedName, attributes: Vec<OwnedAttribute>) -> Self {
         Self {
             prefix: name.prefix,
+            namespace: name.namespace,
             name: name.local_name,
             attributes: attributes.into_iter().map(Attribute::from).collect(),
         }
     }
 
     pub fn qname(&self) -> String {
-        if let Some(prefix) = &self.prefix {
-            format!("{}:{}", prefix, &self.name)
-        } else {
-            self.name.clone()
+        self.resolved_name().qname()
+    }
+
+    pub fn resolved_name(&self) -> Name<'_> {
+        Name {
+            prefix: self.prefix.as_deref(),
+            namespace: self.namespace.as_deref(),
+            local: &self.name,
+        }
+    }
+
+    /// Whether the element is an explicit null, marked with `xsi:nil="true"`
+    pub fn is_nil(&self) -> bool {
+        self.attributes.iter().any(|attribute| {
+            attribute.namespace.as_deref() == Some(XSI_NAMESPACE)
+                && attribute.name == "nil"
+                && matches!(attribute.value.trim(), "true" | "1")
+        })
+    }
+
+    /// Fail on the attributes of an element whose attributes are not deserialized, when unknown
+    /// content is denied
+    pub fn deny_attributes(&self, config: &SerdeXml) -> Result<()> {
+        if !config.denies_unknown() {
+            return Ok(());
+        }
+        match self.attributes.iter().find(|attribute| !attribute.is_xsi()) {
+            Some(attribute) => Err(Error::UnknownContent(format!(
+                "attribute '{}'",
+                attribute.qname()
+            ))
+            .within(Step::Attribute(attribute.qname()))),
+            None => Ok(()),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Attribute {
     pub prefix: Option<String>,
+    pub namespace: Option<String>,
     pub name: String,
     pub value: String,
+    /// Where the value appears verbatim in the input, if it can be borrowed from it
+    pub span: Option<Range<usize>>,
 }
 
 impl Attribute {
     pub fn qname(&self) -> String {
-        if let Some(prefix) = &self.prefix {
-            format!("{}:{}", prefix, &self.name)
-        } else {
-            self.name.clone()
+        self.resolved_name().qname()
+    }
+
+    pub fn resolved_name(&self) -> Name<'_> {
+        Name {
+            prefix: self.prefix.as_deref(),
+            namespace: self.namespace.as_deref(),
+            local: &self.name,
         }
     }
+
+    /// Whether the attribute is in the XML Schema instance namespace, such as
+    /// `xsi:schemaLocation`, which instructs processors rather than holding content
+    pub fn is_xsi(&self) -> bool {
+        self.namespace.as_deref() == Some(XSI_NAMESPACE)
+    }
 }
 
 impl From<OwnedAttribute> for Attribute {
     fn from(value: OwnedAttribute) -> Self {
         Self {
             prefix: value.name.prefix,
+            namespace: value.name.namespace,
             name: value.name.local_name,
             value: value.value,
+            span: None,
         }
     }
 }
 
-pub fn parse_xml_bool(text: &str) -> Result<bool> {
-    match text {
-        "true" | "1" => Ok(true),
-        "false" | "0" => Ok(false),
-        s => Err(Error::Unexpected {
-            expected: "boolean",
-            but_got: s.to_string(),
-        }),
-    }
-}
-
-pub trait Reader<R: Read> {
+pub trait Reader<R: Read, B> {
     /// Look at the next event without consuming it
     fn peek(&mut self) -> Result<&Event>;
 
@@ -85,12 +144,18 @@ pub trait Reader<R: Read> {
     /// Consume the next event
     fn next(&mut self) -> Result<Event>;
     /// Create a child buffer whose cursor starts at the same position as this buffer.
-    fn child(&mut self) -> ChildReader<R>;
-
-    /// Consume the next event as a string
-    fn chars(&mut self) -> Result<String> {
+    fn child(&mut self) -> ChildReader<'_, R, B>;
+    /// Where the next event appears in the document, unless it was replayed
+    fn position(&mut self) -> Result<Option<Position>>;
+    /// The configuration of the deserializer
+    fn config(&self) -> &SerdeXml;
+    /// The path to the content being deserialized, and the content skipped so far
+    fn collector(&mut self) -> &mut Collector;
+
+    /// Consume the next event as text
+    fn text(&mut self) -> Result<Text> {
         match self.next()? {
-            Event::Text(cs) => Ok(cs),
+            Event::Text(text) => Ok(text),
             event => Err(Error::Unexpected {
                 expected: "text",
                 but_got: event.to_string(),
@@ -98,9 +163,9 @@ pub trait Reader<R: Read> {
         }
     }
 
-    /// Consume the next event as a boolean
-    fn bool(&mut self) -> Result<bool> {
-        parse_xml_bool(self.chars()?.as_str())
+    /// Consume the next event as a string
+    fn chars(&mut self) -> Result<String> {
+        Ok(self.text()?.value)
     }
 
     /// Consume the next event as a start of element
@@ -114,6 +179,25 @@ pub trait Reader<R: Read> {
         }
     }
 
+    /// Consume the next event as a start of element whose attributes are not deserialized
+    fn start_element_without_attributes(&mut self) -> Result<()> {
+        let position = self.position()?;
+        let element = self.start_element()?;
+        element.deny_attributes(self.config())?;
+        for attribute in element
+            .attributes
+            .iter()
+            .filter(|attribute| !attribute.is_xsi())
+        {
+            self.collector().record(
+                IgnoredKind::Attribute,
+                Some(Step::Attribute(attribute.qname())),
+                position,
+            );
+        }
+        Ok(())
+    }
+
     /// Consume the next event as an end of element
     fn end_element(&mut self) -> Result<()> {
         match self.next()? {
@@ -126,46 +210,91 @@ pub trait Reader<R: Read> {
     }
 }
 
-fn next_significant_event<R: Read>(xml_reader: &mut EventReader<R>) -> Result<Event> {
+/// An event, and where it appears in the document unless it was replayed
+type Located = (Event, Option<Position>);
+
+fn next_significant_event<'de, R: Read, B: Input<'de, R>>(
+    xml_reader: &mut EventReader<R>,
+    input: &mut B,
+) -> Result<Located> {
     let event = loop {
         let event = xml_reader.next()?;
         match event {
             XmlEvent::StartElement {
                 name, attributes, ..
             } => {
-                break Event::StartElement(Element::from(name, attributes));
+                let mut element = Element::from(name, attributes);
+                if let Some(region) = input.region(xml_reader.source()) {
+                    for attribute in &mut element.attributes {
+                        attribute.span = input.find(&region, &attribute.value);
+                    }
+                }
+                break Event::StartElement(element);
             }
             XmlEvent::EndElement { .. } => break Event::EndElement,
-            XmlEvent::Characters(s) | XmlEvent::CData(s) => break Event::Text(s),
+            XmlEvent::Characters(s) | XmlEvent::CData(s) => {
+                let span = input
+                    .region(xml_reader.source())
+                    .and_then(|region| input.find(&region, &s));
+                break Event::Text(Text { value: s, span });
+            }
             XmlEvent::EndDocument => break Event::Eof,
             _ => (),
         };
     };
-    Ok(event)
+    Ok((event, Some(xml_reader.position().into())))
 }
 
-pub struct RootReader<R: Read> {
+pub struct RootReader<R: Read, B = OwnedInput> {
     xml_reader: EventReader<R>,
-    lookahead: VecDeque<Event>,
-    overlapping_sequences: bool,
+    input: B,
+    lookahead: VecDeque<Located>,
+    pub config: SerdeXml,
+    pub collector: Collector,
 }
 
-impl<R: Read> RootReader<R> {
-    pub fn new(xml_reader: EventReader<R>, overlapping_sequences: bool) -> Self {
+impl<R: Read, B> RootReader<R, B> {
+    pub fn new(xml_reader: EventReader<R>, input: B, config: SerdeXml) -> Self {
         Self {
             xml_reader,
+            input,
             lookahead: VecDeque::new(),
-            overlapping_sequences,
+            config,
+            collector: Collector::default(),
+        }
+    }
+}
+
+impl<R: Read, B> RootReader<R, B> {
+    /// Drop the events that were read ahead, such as the rest of a record that failed to
+    /// deserialize
+    pub fn skip_buffered(&mut self) {
+        self.lookahead.clear();
+    }
+}
+
+impl RootReader<std::io::Empty> {
+    /// Create a reader over events that were buffered beforehand
+    pub fn replay(events: Vec<Event>, config: SerdeXml) -> Self {
+        Self {
+            xml_reader: EventReader::new(std::io::empty()),
+            input: OwnedInput,
+            lookahead: events.into_iter().map(|event| (event, None)).collect(),
+            config,
+            collector: Collector::default(),
         }
     }
 }
 
-impl<R: Read> Reader<R> for RootReader<R> {
-    fn child(&mut self) -> ChildReader<R> {
+impl<'de, R: Read, B: Input<'de, R>> Reader<R, B> for RootReader<R, B> {
+    fn child(&mut self) -> ChildReader<'_, R, B> {
         ChildReader {
             xml_reader: &mut self.xml_reader,
+            input: &mut self.input,
             lookahead: &mut self.lookahead,
-            overlapping_sequences: self.overlapping_sequences,
+            overlapping_sequences: self.config.overlapping_sequences,
+            config: &self.config,
+            collector: &mut self.collector,
             cursor: 0,
         }
     }
@@ -176,38 +305,61 @@ impl<R: Read> Reader<R> for RootReader<R> {
 
     fn peek_nth(&mut self, n: usize) -> Result<&Event> {
         while self.lookahead.len() <= n {
-            self.lookahead
-                .push_back(next_significant_event(&mut self.xml_reader)?);
+            self.lookahead.push_back(next_significant_event(
+                &mut self.xml_reader,
+                &mut self.input,
+            )?);
         }
-        Ok(&self.lookahead[n])
+        Ok(&self.lookahead[n].0)
     }
 
     fn take_nth(&mut self, n: usize) -> Result<Event> {
         self.peek_nth(n)?;
-        let event = self.lookahead.remove(n).unwrap();
+        let (event, _) = self.lookahead.remove(n).unwrap();
         trace!("EVENT: {event:?}");
         Ok(event)
     }
 
     fn next(&mut self) -> Result<Event> {
-        let event = if self.lookahead.is_empty() {
-            next_significant_event(&mut self.xml_reader)?
+        let (event, _) = if self.lookahead.is_empty() {
+            next_significant_event(&mut self.xml_reader, &mut self.input)?
         } else {
             self.lookahead.pop_front().unwrap()
         };
         trace!("EVENT: {event:?}");
         Ok(event)
     }
+
+    fn position(&mut self) -> Result<Option<Position>> {
+        self.peek()?;
+        Ok(self.lookahead[0].1)
+    }
+
+    fn config(&self) -> &SerdeXml {
+        &self.config
+    }
+
+    fn collector(&mut self) -> &mut Collector {
+        &mut self.collector
+    }
 }
 
-pub struct ChildReader<'r, R: Read> {
+pub struct ChildReader<'r, R: Read, B = OwnedInput> {
     xml_reader: &'r mut EventReader<R>,
-    lookahead: &'r mut VecDeque<Event>,
+    input: &'r mut B,
+    lookahead: &'r mut VecDeque<Located>,
     pub overlapping_sequences: bool,
+    pub config: &'r SerdeXml,
+    pub collector: &'r mut Collector,
     cursor: usize,
 }
 
-impl<R: Read> ChildReader<'_, R> {
+impl<'de, R: Read, B: Input<'de, R>> ChildReader<'_, R, B> {
+    /// Borrow the given text from the input, if it appears there verbatim
+    pub fn lend(&self, span: &Option<Range<usize>>) -> Option<&'de str> {
+        span.as_ref().and_then(|span| self.input.lend(span))
+    }
+
     /// Consume the next element
     pub fn ignore(&mut self) -> Result<()> {
         self.start_element()?;
@@ -228,6 +380,121 @@ impl<R: Read> ChildReader<'_, R> {
         Ok(())
     }
 
+    /// Consume the next element and return its events
+    pub fn capture_element(&mut self) -> Result<Vec<Event>> {
+        let mut events = vec![Event::StartElement(self.start_element()?)];
+        let mut depth = 1usize;
+        while depth > 0 {
+            let event = self.next()?;
+            match event {
+                Event::Eof => {
+                    return Err(Error::Unexpected {
+                        expected: "anything",
+                        but_got: event.to_string(),
+                    });
+                }
+                Event::EndElement => depth -= 1,
+                Event::StartElement(_) => depth += 1,
+                Event::Text(_) => (),
+            }
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    /// Consume the remaining content of the current element, up to its end, and return its events
+    pub fn capture_content(&mut self) -> Result<Vec<Event>> {
+        let mut events = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            match self.peek()? {
+                event @ Event::Eof => {
+                    return Err(Error::Unexpected {
+                        expected: "anything",
+                        but_got: event.to_string(),
+                    });
+                }
+                Event::EndElement if depth == 0 => break,
+                Event::EndElement => depth -= 1,
+                Event::StartElement(_) => depth += 1,
+                Event::Text(_) => (),
+            }
+            events.push(self.next()?);
+        }
+        Ok(events)
+    }
+
+    /// Consume the text nodes that follow among the remaining content of the current element,
+    /// leaving the elements around them in place
+    pub fn take_sibling_texts(&mut self) -> Result<Vec<Text>> {
+        let mut texts = Vec::new();
+        let mut n = self.cursor;
+        let mut depth = 0usize;
+        loop {
+            match self.peek_nth(n)? {
+                event @ Event::Eof => {
+                    return Err(Error::Unexpected {
+                        expected: "anything",
+                        but_got: event.to_string(),
+                    });
+                }
+                Event::EndElement if depth == 0 => return Ok(texts),
+                Event::EndElement => depth -= 1,
+                Event::StartElement(_) => depth += 1,
+                Event::Text(_) if depth == 0 => {
+                    if let Event::Text(text) = self.take_nth(n)? {
+                        texts.push(text);
+                    }
+                    continue;
+                }
+                Event::Text(_) => (),
+            }
+            n += 1;
+        }
+    }
+
+    /// Check whether the next element has no attributes and contains at most text
+    pub fn is_leaf_element(&mut self) -> Result<bool> {
+        match self.peek()? {
+            Event::StartElement(element) if element.attributes.is_empty() => {
+                self.is_text_content_at(self.cursor + 1)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Check whether the remaining content of the current element is at most text
+    pub fn is_text_content(&mut self) -> Result<bool> {
+        self.is_text_content_at(self.cursor)
+    }
+
+    fn is_text_content_at(&mut self, n: usize) -> Result<bool> {
+        Ok(match self.peek_nth(n)? {
+            Event::EndElement => true,
+            Event::Text(_) => matches!(self.peek_nth(n + 1)?, Event::EndElement),
+            _ => false,
+        })
+    }
+
+    /// Check whether a sibling element with the given name follows the next element
+    pub fn has_sibling_named(&mut self, element_name: &str) -> Result<bool> {
+        let config = self.config;
+        let mut child = self.child();
+        child.fast_forward()?;
+        loop {
+            match child.peek()? {
+                Event::StartElement(element)
+                    if element.resolved_name().canonical(&config.namespaces) == element_name =>
+                {
+                    return Ok(true)
+                }
+                Event::StartElement(_) => child.fast_forward()?,
+                Event::Text(_) => child.cursor += 1,
+                Event::EndElement | Event::Eof => return Ok(false),
+            }
+        }
+    }
+
     /// Advance the child buffer without consuming the events
     pub fn fast_forward(&mut self) -> Result<()> {
         self.cursor += 1;
@@ -250,7 +517,7 @@ impl<R: Read> ChildReader<'_, R> {
     }
 }
 
-impl<R: Read> Reader<R> for ChildReader<'_, R> {
+impl<'de, R: Read, B: Input<'de, R>> Reader<R, B> for ChildReader<'_, R, B> {
     fn peek(&mut self) -> Result<&Event> {
         self.peek_nth(self.cursor)
     }
@@ -258,14 +525,14 @@ impl<R: Read> Reader<R> for ChildReader<'_, R> {
     fn peek_nth(&mut self, n: usize) -> Result<&Event> {
         while self.lookahead.len() <= n {
             self.lookahead
-                .push_back(next_significant_event(self.xml_reader)?);
+                .push_back(next_significant_event(self.xml_reader, self.input)?);
         }
-        Ok(&self.lookahead[n])
+        Ok(&self.lookahead[n].0)
     }
 
     fn take_nth(&mut self, n: usize) -> Result<Event> {
         self.peek_nth(n)?;
-        let event = self.lookahead.remove(n).unwrap();
+        let (event, _) = self.lookahead.remove(n).unwrap();
         trace!("EVENT: {event:?}");
         Ok(event)
     }
@@ -274,11 +541,27 @@ impl<R: Read> Reader<R> for ChildReader<'_, R> {
         self.take_nth(self.cursor)
     }
 
-    fn child(&mut self) -> ChildReader<R> {
+    fn position(&mut self) -> Result<Option<Position>> {
+        self.peek()?;
+        Ok(self.lookahead[self.cursor].1)
+    }
+
+    fn config(&self) -> &SerdeXml {
+        self.config
+    }
+
+    fn collector(&mut self) -> &mut Collector {
+        self.collector
+    }
+
+    fn child(&mut self) -> ChildReader<'_, R, B> {
         ChildReader {
             xml_reader: self.xml_reader,
+            input: self.input,
             lookahead: self.lookahead,
             overlapping_sequences: self.overlapping_sequences,
+            config: self.config,
+            collector: self.collector,
             cursor: self.cursor,
         }
     }
@@ -0,0 +1,607 @@
+use super::{
+    child::ChildDeserializer,
+    plain::PlainTextDeserializer,
+    reader::{Attribute, Element, Event, Reader, RootReader, Text},
+};
+use crate::{
+    config::{BinaryEncoding, Booleans, Dialect, Markers, NameMatching, Namespaces, SerdeXml},
+    error::{Error, Result},
+};
+use log::trace;
+use serde::{
+    de::{
+        value::SeqDeserializer, Deserialize, DeserializeOwned, Deserializer, IntoDeserializer,
+        SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+/// Name of the newtype struct through which deserializers hand buffered events over to [Replay].
+pub(crate) const REPLAY: &str = "$serde_xml_rs::Replay";
+
+/// A buffered element subtree, or attribute value, that can be deserialized several times.
+///
+/// This is mostly useful to implement untagged enums, by trying each variant against the same
+/// input and keeping the first one that matches. Unlike `#[serde(untagged)]`, which buffers
+/// everything as strings, each attempt sees the original XML, so numbers, booleans and nested
+/// structs deserialize as usual.
+///
+/// `Replay` can only be deserialized by this crate. It keeps the configuration of the
+/// deserializer it was captured by, such as boolean spellings, namespaces and markers, to
+/// deserialize the buffered input the same way.
+///
+/// ```rust
+/// # use serde::{Deserialize, Deserializer};
+/// # use serde_xml_rs::de::Replay;
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Detailed {
+///     amount: u32,
+///     currency: String,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Price {
+///     Amount(u32),
+///     Detailed(Detailed),
+/// }
+///
+/// impl<'de> Deserialize<'de> for Price {
+///     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+///         let replay = Replay::deserialize(deserializer)?;
+///         replay
+///             .deserialize_as()
+///             .map(Price::Amount)
+///             .or_else(|_| replay.deserialize_as().map(Price::Detailed))
+///             .map_err(serde::de::Error::custom)
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Item {
+///     price: Price,
+/// }
+///
+/// # fn main() {
+/// let item: Item = serde_xml_rs::from_str("<item><price>12</price></item>").unwrap();
+/// assert_eq!(item.price, Price::Amount(12));
+///
+/// let text = "<item><price><amount>12</amount><currency>EUR</currency></price></item>";
+/// let item: Item = serde_xml_rs::from_str(text).unwrap();
+/// assert_eq!(
+///     item.price,
+///     Price::Detailed(Detailed { amount: 12, currency: "EUR".to_string() })
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Replay {
+    captured: Captured,
+    config: SerdeXml,
+}
+
+impl PartialEq for Replay {
+    fn eq(&self, other: &Self) -> bool {
+        self.captured == other.captured
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Captured {
+    /// A whole element, from its start to its end
+    Element(String, Vec<Event>),
+    /// The remaining content of an element, without its start and end
+    Content(Vec<Event>),
+    /// An attribute value or text node
+    Text(String),
+}
+
+impl Replay {
+    /// Deserialize a value from the buffered input.
+    pub fn deserialize_as<T: DeserializeOwned>(&self) -> Result<T> {
+        trace!("replay");
+        match &self.captured {
+            Captured::Element(element_name, events) => {
+                let mut reader = RootReader::replay(events.clone(), self.config.clone());
+                T::deserialize(
+                    ChildDeserializer::new_with_element_name(reader.child(), element_name.clone())
+                        .without_step(),
+                )
+            }
+            Captured::Content(events) => {
+                let mut events = events.clone();
+                events.push(Event::EndElement);
+                let mut reader = RootReader::replay(events, self.config.clone());
+                T::deserialize(ChildDeserializer::new(reader.child()))
+            }
+            Captured::Text(text) => T::deserialize(PlainTextDeserializer::new(text, &self.config)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Replay {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(REPLAY, ReplayVisitor)
+    }
+}
+
+struct ReplayVisitor;
+
+impl<'de> Visitor<'de> for ReplayVisitor {
+    type Value = Replay;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("XML input buffered by serde-xml-rs")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let token = Token::deserialize(deserializer)?;
+        Replay::from_token(token)
+            .ok_or_else(|| serde::de::Error::custom("XML input not buffered by serde-xml-rs"))
+    }
+}
+
+/// Hand a whole element over to a [Replay] visitor
+pub(crate) fn visit_element<'de, V: Visitor<'de>>(
+    visitor: V,
+    config: &SerdeXml,
+    element_name: String,
+    events: Vec<Event>,
+) -> Result<V::Value> {
+    visit(visitor, config, Captured::Element(element_name, events))
+}
+
+/// Hand the content of an element over to a [Replay] visitor
+pub(crate) fn visit_content<'de, V: Visitor<'de>>(
+    visitor: V,
+    config: &SerdeXml,
+    events: Vec<Event>,
+) -> Result<V::Value> {
+    visit(visitor, config, Captured::Content(events))
+}
+
+/// Hand an attribute value or text node over to a [Replay] visitor
+pub(crate) fn visit_text<'de, V: Visitor<'de>>(
+    visitor: V,
+    config: &SerdeXml,
+    text: &str,
+) -> Result<V::Value> {
+    visit(visitor, config, Captured::Text(text.to_string()))
+}
+
+fn visit<'de, V: Visitor<'de>>(
+    visitor: V,
+    config: &SerdeXml,
+    captured: Captured,
+) -> Result<V::Value> {
+    let replay = Replay {
+        captured,
+        config: config.clone(),
+    };
+    visitor.visit_newtype_struct(replay.into_token())
+}
+
+/// A value of the serde data model. Visitors only receive such values, so a [Replay] is handed
+/// over to its visitor as a token, which the visitor turns back into a [Replay].
+#[derive(Debug)]
+enum Token {
+    Bool(bool),
+    U8(u8),
+    String(String),
+    Option(Option<Box<Token>>),
+    Seq(Vec<Token>),
+}
+
+impl<'de> Deserializer<'de> for Token {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Token::Bool(value) => visitor.visit_bool(value),
+            Token::U8(value) => visitor.visit_u8(value),
+            Token::String(value) => visitor.visit_string(value),
+            Token::Option(None) => visitor.visit_none(),
+            Token::Option(Some(token)) => visitor.visit_some(*token),
+            Token::Seq(tokens) => visitor.visit_seq(SeqDeserializer::new(tokens.into_iter())),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl IntoDeserializer<'_, Error> for Token {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserialize<'de> for Token {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(TokenVisitor)
+    }
+}
+
+struct TokenVisitor;
+
+impl<'de> Visitor<'de> for TokenVisitor {
+    type Value = Token;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("XML input buffered by serde-xml-rs")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, value: bool) -> std::result::Result<Token, E> {
+        Ok(Token::Bool(value))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> std::result::Result<Token, E> {
+        u8::try_from(value)
+            .map(Token::U8)
+            .map_err(|_| E::custom("XML input not buffered by serde-xml-rs"))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> std::result::Result<Token, E> {
+        Ok(Token::String(value.to_string()))
+    }
+
+    fn visit_string<E: serde::de::Error>(self, value: String) -> std::result::Result<Token, E> {
+        Ok(Token::String(value))
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> std::result::Result<Token, E> {
+        Ok(Token::Option(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> std::result::Result<Token, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Token::deserialize(deserializer).map(|token| Token::Option(Some(Box::new(token))))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Token, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut tokens = Vec::new();
+        while let Some(token) = seq.next_element()? {
+            tokens.push(token);
+        }
+        Ok(Token::Seq(tokens))
+    }
+}
+
+/// A value that is handed over to a [Replay] visitor as a [Token]
+trait Handoff: Sized {
+    fn into_token(self) -> Token;
+
+    fn from_token(token: Token) -> Option<Self>;
+}
+
+impl Handoff for Token {
+    fn into_token(self) -> Token {
+        self
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        Some(token)
+    }
+}
+
+impl Handoff for bool {
+    fn into_token(self) -> Token {
+        Token::Bool(self)
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        match token {
+            Token::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Handoff for u8 {
+    fn into_token(self) -> Token {
+        Token::U8(self)
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        match token {
+            Token::U8(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Handoff for String {
+    fn into_token(self) -> Token {
+        Token::String(self)
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        match token {
+            Token::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Handoff> Handoff for Option<T> {
+    fn into_token(self) -> Token {
+        Token::Option(self.map(|value| Box::new(value.into_token())))
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        match token {
+            Token::Option(None) => Some(None),
+            Token::Option(Some(token)) => T::from_token(*token).map(Some),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Handoff> Handoff for Vec<T> {
+    fn into_token(self) -> Token {
+        Token::Seq(self.into_iter().map(Handoff::into_token).collect())
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        match token {
+            Token::Seq(tokens) => tokens.into_iter().map(T::from_token).collect(),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! tuple_handoff {
+    ($($field:ident: $type:ident),*) => {
+        impl<$($type: Handoff),*> Handoff for ($($type,)*) {
+            fn into_token(self) -> Token {
+                let ($($field,)*) = self;
+                Token::Seq(vec![$($field.into_token()),*])
+            }
+
+            fn from_token(token: Token) -> Option<Self> {
+                let Token::Seq(tokens) = token else {
+                    return None;
+                };
+                let mut tokens = tokens.into_iter();
+                let value = ($($type::from_token(tokens.next()?)?,)*);
+                tokens.next().is_none().then_some(value)
+            }
+        }
+    };
+}
+
+tuple_handoff!();
+tuple_handoff!(a: A, b: B);
+tuple_handoff!(a: A, b: B, c: C, d: D);
+tuple_handoff!(a: A, b: B, c: C, d: D, e: E);
+
+/// Hand a unit-only enum over as the index of its variant, with its variants listed in the order
+/// in which they are declared
+macro_rules! unit_enum_handoff {
+    ($type:ident { $($variant:ident),* }) => {
+        impl Handoff for $type {
+            fn into_token(self) -> Token {
+                Token::U8(self as u8)
+            }
+
+            fn from_token(token: Token) -> Option<Self> {
+                let variants = [$($type::$variant),*];
+                variants.get(usize::from(u8::from_token(token)?)).copied()
+            }
+        }
+    };
+}
+
+unit_enum_handoff!(BinaryEncoding { Utf8, Base64, Hex });
+unit_enum_handoff!(Dialect { Current, Legacy });
+unit_enum_handoff!(NameMatching {
+    Exact,
+    IgnoreAsciiCase,
+    LocalName
+});
+
+impl Handoff for Replay {
+    fn into_token(self) -> Token {
+        (self.captured, self.config).into_token()
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let (captured, config) = Handoff::from_token(token)?;
+        Some(Replay { captured, config })
+    }
+}
+
+impl Handoff for Captured {
+    fn into_token(self) -> Token {
+        match self {
+            Captured::Element(element_name, events) => (0u8, (element_name, events)).into_token(),
+            Captured::Content(events) => (1u8, events).into_token(),
+            Captured::Text(text) => (2u8, text).into_token(),
+        }
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let (variant, token): (u8, Token) = Handoff::from_token(token)?;
+        match variant {
+            0 => Handoff::from_token(token)
+                .map(|(element_name, events)| Captured::Element(element_name, events)),
+            1 => Handoff::from_token(token).map(Captured::Content),
+            2 => Handoff::from_token(token).map(Captured::Text),
+            _ => None,
+        }
+    }
+}
+
+impl Handoff for Event {
+    fn into_token(self) -> Token {
+        match self {
+            Event::StartElement(element) => (0u8, element).into_token(),
+            Event::Text(text) => (1u8, text.value).into_token(),
+            Event::EndElement | Event::Eof => (2u8, ()).into_token(),
+        }
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let (variant, token): (u8, Token) = Handoff::from_token(token)?;
+        match variant {
+            0 => Handoff::from_token(token).map(Event::StartElement),
+            1 => Handoff::from_token(token).map(|value| Event::Text(Text::new(value))),
+            2 => <()>::from_token(token).map(|()| Event::EndElement),
+            _ => None,
+        }
+    }
+}
+
+impl Handoff for Element {
+    fn into_token(self) -> Token {
+        (self.prefix, self.namespace, self.name, self.attributes).into_token()
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let (prefix, namespace, name, attributes) = Handoff::from_token(token)?;
+        Some(Element {
+            prefix,
+            namespace,
+            name,
+            attributes,
+        })
+    }
+}
+
+impl Handoff for Attribute {
+    fn into_token(self) -> Token {
+        (self.prefix, self.namespace, self.name, self.value).into_token()
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let (prefix, namespace, name, value) = Handoff::from_token(token)?;
+        Some(Attribute {
+            prefix,
+            namespace,
+            name,
+            value,
+            span: None,
+        })
+    }
+}
+
+/// Only the settings that apply to deserialization are handed over, since the buffered input is
+/// neither parsed nor written again
+impl Handoff for SerdeXml {
+    fn into_token(self) -> Token {
+        Token::Seq(vec![
+            self.namespaces.into_token(),
+            self.overlapping_sequences.into_token(),
+            self.binary_encoding.into_token(),
+            self.booleans.into_token(),
+            self.none_as_nil.into_token(),
+            self.markers.into_token(),
+            self.name_matching.into_token(),
+            self.root_element_name.into_token(),
+            self.strict.into_token(),
+            self.verify_root_name.into_token(),
+            self.verify_end.into_token(),
+            self.deny_unknown.into_token(),
+            self.verify_order.into_token(),
+        ])
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let Token::Seq(tokens) = token else {
+            return None;
+        };
+        let mut tokens = tokens.into_iter();
+        let mut next = || tokens.next();
+        Some(SerdeXml {
+            namespaces: Handoff::from_token(next()?)?,
+            overlapping_sequences: Handoff::from_token(next()?)?,
+            binary_encoding: Handoff::from_token(next()?)?,
+            booleans: Handoff::from_token(next()?)?,
+            none_as_nil: Handoff::from_token(next()?)?,
+            markers: Handoff::from_token(next()?)?,
+            name_matching: Handoff::from_token(next()?)?,
+            root_element_name: Handoff::from_token(next()?)?,
+            strict: Handoff::from_token(next()?)?,
+            verify_root_name: Handoff::from_token(next()?)?,
+            verify_end: Handoff::from_token(next()?)?,
+            deny_unknown: Handoff::from_token(next()?)?,
+            verify_order: Handoff::from_token(next()?)?,
+            ..SerdeXml::default()
+        })
+    }
+}
+
+impl Handoff for Namespaces {
+    fn into_token(self) -> Token {
+        self.mapping.into_iter().collect::<Vec<_>>().into_token()
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let mapping: Vec<(String, String)> = Handoff::from_token(token)?;
+        Some(Namespaces {
+            mapping: mapping.into_iter().collect(),
+        })
+    }
+}
+
+impl Handoff for Booleans {
+    fn into_token(self) -> Token {
+        (
+            self.true_values,
+            self.false_values,
+            self.case_insensitive,
+            self.true_output,
+            self.false_output,
+        )
+            .into_token()
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let (true_values, false_values, case_insensitive, true_output, false_output) =
+            Handoff::from_token(token)?;
+        Some(Booleans {
+            true_values,
+            false_values,
+            case_insensitive,
+            true_output,
+            false_output,
+        })
+    }
+}
+
+impl Handoff for Markers {
+    fn into_token(self) -> Token {
+        (self.attribute, self.text, self.content, self.dialect).into_token()
+    }
+
+    fn from_token(token: Token) -> Option<Self> {
+        let (attribute, text, content, dialect) = Handoff::from_token(token)?;
+        Some(Markers {
+            attribute,
+            text,
+            content,
+            dialect,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use crate::error::{Path, Position, Step};
+
+/// What kind of content was skipped while deserializing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IgnoredKind {
+    /// An element, along with everything it contains
+    Element,
+    /// An attribute
+    Attribute,
+    /// Text content
+    Text,
+    /// Content buffered for the flattened fields of a struct, which may have left it out
+    Unchecked,
+}
+
+/// Content of a document that no field deserialized and that was skipped, as reported by
+/// [SerdeXml::from_str_with_report](crate::SerdeXml::from_str_with_report).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ignored {
+    pub kind: IgnoredKind,
+    /// Path from the document root to the skipped content
+    pub path: Path,
+    /// Where the skipped content appears in the document, if known. Attributes are located by
+    /// their element.
+    pub position: Option<Position>,
+}
+
+/// Keeps track of the path to the content being deserialized, and of the content that is
+/// skipped, when enabled
+#[derive(Debug, Default)]
+pub(crate) struct Collector {
+    enabled: bool,
+    /// Steps from the document root, with where each appears in the document
+    path: Vec<(Step, Option<Position>)>,
+    ignored: Vec<Ignored>,
+}
+
+impl Collector {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Step into some content, returning the mark to leave it with
+    pub fn enter(&mut self, step: impl FnOnce() -> Step, position: Option<Position>) -> usize {
+        let mark = self.path.len();
+        if self.enabled {
+            self.path.push((step(), position));
+        }
+        mark
+    }
+
+    /// Step out of the content entered with the given mark, and of anything left entered within
+    /// it when deserialization failed
+    pub fn leave(&mut self, mark: usize) {
+        self.path.truncate(mark);
+    }
+
+    /// Where the innermost content entered appears in the document
+    pub fn position(&self) -> Option<Position> {
+        self.path.last().and_then(|(_, position)| *position)
+    }
+
+    /// Record skipped content at the current path, or at a step from it
+    pub fn record(&mut self, kind: IgnoredKind, step: Option<Step>, position: Option<Position>) {
+        if !self.enabled {
+            return;
+        }
+        let path = step
+            .into_iter()
+            .chain(self.path.iter().rev().map(|(step, _)| step.clone()))
+            .fold(Path::default(), |mut path, step| {
+                path.push_front(step);
+                path
+            });
+        self.ignored.push(Ignored {
+            kind,
+            path,
+            position,
+        });
+    }
+
+    /// The content skipped so far, leaving none recorded
+    pub fn take(&mut self) -> Vec<Ignored> {
+        std::mem::take(&mut self.ignored)
+    }
+}
@@ -1,26 +1,31 @@
 use super::{
     child::ChildDeserializer,
+    input::Input,
+    plain::PlainTextDeserializer,
     reader::{ChildReader, Event, Reader},
 };
-use crate::error::{Error, Result};
+use crate::error::{Error, Result, Step};
 use log::trace;
 use std::io::Read;
 
-pub struct SeqAccess<'a, R: Read> {
-    reader: ChildReader<'a, R>,
+pub struct SeqAccess<'a, R: Read, B> {
+    reader: ChildReader<'a, R, B>,
     element_name: Option<String>,
+    /// Number of items deserialized so far
+    count: usize,
 }
 
-impl<'a, R: Read> SeqAccess<'a, R> {
-    pub fn new(reader: ChildReader<'a, R>, element_name: Option<String>) -> Self {
+impl<'a, R: Read, B> SeqAccess<'a, R, B> {
+    pub fn new(reader: ChildReader<'a, R, B>, element_name: Option<String>) -> Self {
         Self {
             reader,
             element_name,
+            count: 0,
         }
     }
 }
 
-impl<'de, R: Read> serde::de::SeqAccess<'de> for SeqAccess<'_, R> {
+impl<'de, R: Read, B: Input<'de, R>> serde::de::SeqAccess<'de> for SeqAccess<'_, R, B> {
     type Error = Error;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
@@ -29,28 +34,47 @@ impl<'de, R: Read> serde::de::SeqAccess<'de> for SeqAccess<'_, R> {
     {
         trace!("next element");
         let overlapping_sequences = self.reader.overlapping_sequences;
+        let config = self.reader.config;
         loop {
+            let position = self.reader.position()?;
             match (&self.element_name, self.reader.peek()?) {
                 (Some(element_name), Event::StartElement(element))
-                    if &element.qname() == element_name =>
+                    if element.resolved_name().matches(element_name, config) =>
                 {
-                    break seed
-                        .deserialize(ChildDeserializer::new_with_element_name(
-                            self.reader.child(),
-                            element_name.to_string(),
-                        ))
-                        .map(Some);
+                    self.count += 1;
+                    let step = Step::Element {
+                        name: element.qname(),
+                        index: Some(self.count),
+                    };
+                    let mark = self.reader.collector.enter(|| step.clone(), position);
+                    let value = seed
+                        .deserialize(
+                            ChildDeserializer::new_with_element_name(
+                                self.reader.child(),
+                                element_name.to_string(),
+                            )
+                            .without_step(),
+                        )
+                        .map(Some)
+                        .map_err(|error| error.at(position).within(step));
+                    self.reader.collector.leave(mark);
+                    break value;
                 }
                 (Some(_), Event::StartElement(_)) if overlapping_sequences => {
                     trace!("ff {}", self.reader.peek()?);
                     self.reader.fast_forward()?;
                 }
                 (None, Event::StartElement(_)) => {
-                    break match seed.deserialize(ChildDeserializer::new(self.reader.child())) {
+                    let value = seed.deserialize(ChildDeserializer::new(self.reader.child()));
+                    break match value {
                         Ok(r) => Ok(Some(r)),
-                        Err(Error::Custom(_) | Error::Unexpected { .. }) => Ok(None),
-                        Err(e) => Err(e),
-                    }
+                        Err(e)
+                            if matches!(e.inner(), Error::Custom(_) | Error::Unexpected { .. }) =>
+                        {
+                            Ok(None)
+                        }
+                        Err(e) => Err(e.at(position)),
+                    };
                 }
                 _ => {
                     trace!("end sequence");
@@ -60,3 +84,74 @@ impl<'de, R: Read> serde::de::SeqAccess<'de> for SeqAccess<'_, R> {
         }
     }
 }
+
+/// Access to the members of a tuple by position. Runs of primitive members are read from
+/// whitespace-separated text, and other members, such as structs and enums, from consecutive
+/// child elements.
+pub struct TupleAccess<'a, 'de, R: Read, B> {
+    reader: ChildReader<'a, R, B>,
+    /// Text that the next primitive members are read from
+    text: String,
+    /// The same text borrowed from the input, if available
+    borrowed: Option<&'de str>,
+    /// Number of child elements deserialized so far
+    count: usize,
+}
+
+impl<'a, 'de, R: Read, B> TupleAccess<'a, 'de, R, B> {
+    pub fn new(reader: ChildReader<'a, R, B>) -> Self {
+        Self {
+            reader,
+            text: String::new(),
+            borrowed: None,
+            count: 0,
+        }
+    }
+}
+
+impl<'de, R: Read, B: Input<'de, R>> serde::de::SeqAccess<'de> for TupleAccess<'_, 'de, R, B> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        trace!("next tuple member");
+        if self.text.is_empty() {
+            let position = self.reader.position()?;
+            match self.reader.peek()? {
+                Event::Text(_) => {
+                    let text = self.reader.text()?;
+                    self.borrowed = self.reader.lend(&text.span);
+                    self.text = text.value;
+                }
+                Event::StartElement(element) => {
+                    self.count += 1;
+                    let step = Step::Element {
+                        name: element.qname(),
+                        index: Some(self.count),
+                    };
+                    let mark = self.reader.collector.enter(|| step.clone(), position);
+                    let value = seed
+                        .deserialize(ChildDeserializer::new(self.reader.child()).without_step())
+                        .map(Some)
+                        .map_err(|error| error.at(position).within(step));
+                    self.reader.collector.leave(mark);
+                    return value;
+                }
+                _ => return Ok(None),
+            }
+        }
+        let (next, remaining) = self.text.split_once(' ').unwrap_or((&self.text, ""));
+        let borrowed = self.borrowed.map(|borrowed| {
+            let (next_borrowed, remaining) = borrowed.split_at(next.len());
+            self.borrowed = Some(remaining.strip_prefix(' ').unwrap_or(remaining));
+            next_borrowed
+        });
+        let value = seed
+            .deserialize(PlainTextDeserializer::new(next, self.reader.config).borrowing(borrowed))
+            .map_err(|error| error.within(Step::Text))?;
+        self.text = remaining.to_string();
+        Ok(Some(value))
+    }
+}
@@ -0,0 +1,143 @@
+use super::{
+    input::Input,
+    reader::{Event, Reader},
+    Deserializer,
+};
+use crate::error::{Result, Step};
+use log::trace;
+use serde::Deserialize;
+use std::{io::Read, marker::PhantomData};
+
+/// An iterator over the consecutive root elements of a document, such as the records of a log
+/// file or a message stream, or over the elements selected by a path, deserialized as values of
+/// type `T`.
+///
+/// Each record is read ahead in full before it is deserialized, but the rest of the document is
+/// discarded as it is read, so that huge documents are processed in constant memory.
+///
+/// If a record does not match `T`, the iterator yields the error and moves on to the next record.
+/// If the input is not well-formed XML, the iterator yields the error and ends, as the following
+/// records cannot be found reliably. This includes an input without any root element.
+///
+/// ```rust
+/// # use serde::Deserialize;
+/// # use serde_xml_rs::Deserializer;
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Event {
+///     #[serde(rename = "@level")]
+///     level: String,
+///     #[serde(rename = "#text")]
+///     message: String,
+/// }
+///
+/// let text = r#"<event level="info">started</event><event level="warn">slow</event>"#;
+/// let events = Deserializer::new_from_reader(text.as_bytes())
+///     .into_iter::<Event>()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(events.len(), 2);
+/// assert_eq!(events[1].message, "slow");
+/// ```
+pub struct StreamDeserializer<'de, R: Read, B, T> {
+    deserializer: Deserializer<R, B>,
+    /// Names of the elements from the root to the records, or none for root elements
+    path: Vec<String>,
+    /// Names of the elements that are open around the reader
+    open: Vec<String>,
+    done: bool,
+    output: PhantomData<fn() -> (T, &'de ())>,
+}
+
+impl<'de, R: Read, B: Input<'de, R>, T: Deserialize<'de>> StreamDeserializer<'de, R, B, T> {
+    pub fn new(deserializer: Deserializer<R, B>) -> Self {
+        Self::new_with_path(deserializer, "")
+    }
+
+    /// Iterate over the elements selected by a path of element names separated by slashes, such
+    /// as `rss/channel/item`
+    pub fn new_with_path(mut deserializer: Deserializer<R, B>, path: &str) -> Self {
+        let path: Vec<String> = path
+            .split('/')
+            .filter(|step| !step.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some(last) = path.last() {
+            // Records are named by the path rather than by their type
+            deserializer.root_element_name = Some(last.clone());
+        }
+        Self {
+            deserializer,
+            path,
+            open: Vec::new(),
+            done: false,
+            output: PhantomData,
+        }
+    }
+
+    fn is_record(&self, element_name: &str) -> bool {
+        match self.path.split_last() {
+            None => self.open.is_empty(),
+            Some((last, parents)) => self.open == parents && last == element_name,
+        }
+    }
+
+    /// Skip events up to the next record and read it ahead, unless the input ends
+    fn buffer_record(&mut self) -> Result<bool> {
+        loop {
+            match self.deserializer.reader.peek()? {
+                Event::Eof => return Ok(false),
+                Event::StartElement(element) => {
+                    let element_name = element.qname();
+                    if self.is_record(&element_name) {
+                        self.deserializer.reader.child().fast_forward()?;
+                        return Ok(true);
+                    }
+                    self.open.push(element_name);
+                }
+                Event::EndElement => {
+                    self.open.pop();
+                }
+                Event::Text(_) => (),
+            }
+            self.deserializer.reader.next()?;
+        }
+    }
+}
+
+impl<'de, R: Read, B: Input<'de, R>, T: Deserialize<'de>> Iterator
+    for StreamDeserializer<'de, R, B, T>
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.buffer_record() {
+            Ok(true) => (),
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Err(error) => {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+        trace!("record");
+        let result = T::deserialize(&mut self.deserializer).map_err(|error| {
+            self.open.iter().rev().fold(error, |error, element_name| {
+                error.within(Step::element(element_name))
+            })
+        });
+        if result.is_err() {
+            self.deserializer.reader.skip_buffered();
+        }
+        Some(result)
+    }
+}
+
+impl<'de, R: Read, B: Input<'de, R>, T: Deserialize<'de>> std::iter::FusedIterator
+    for StreamDeserializer<'de, R, B, T>
+{
+}
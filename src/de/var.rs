@@ -1,38 +1,51 @@
 use super::{
     child::ChildDeserializer,
+    input::Input,
     map::MapAccess,
     plain::PlainTextDeserializer,
     reader::{ChildReader, Event, Reader},
+    seq::TupleAccess,
 };
-use crate::{
-    config::TEXT,
-    error::{Error, Result},
-};
+use crate::error::{Error, Result, Step};
 use log::trace;
 use serde::de::{value::StrDeserializer, IntoDeserializer};
 use std::io::Read;
 
-pub struct EnumAccess<'a, R: Read> {
-    reader: ChildReader<'a, R>,
+pub struct EnumAccess<'a, R: Read, B> {
+    reader: ChildReader<'a, R, B>,
+    variants: &'static [&'static str],
 }
 
-impl<'a, R: Read> EnumAccess<'a, R> {
-    pub fn new(reader: ChildReader<'a, R>) -> Self {
-        Self { reader }
+impl<'a, R: Read, B> EnumAccess<'a, R, B> {
+    pub fn new(reader: ChildReader<'a, R, B>, variants: &'static [&'static str]) -> Self {
+        Self { reader, variants }
     }
 }
 
-impl<'de, 'a, R: Read> serde::de::EnumAccess<'de> for EnumAccess<'a, R> {
+impl<'de, 'a, R: Read, B: Input<'de, R>> serde::de::EnumAccess<'de> for EnumAccess<'a, R, B> {
     type Error = Error;
-    type Variant = VariantAccess<'a, R>;
+    type Variant = VariantAccess<'a, R, B>;
 
     fn variant_seed<V>(mut self, seed: V) -> Result<(V::Value, Self::Variant)>
     where
         V: serde::de::DeserializeSeed<'de>,
     {
-        let element_name = match self.reader.peek()? {
-            Event::StartElement(element) => element.qname(),
-            Event::Text(_) => TEXT.to_string(),
+        let config = self.reader.config;
+        let position = self.reader.position()?;
+        let (variant, element_name) = match self.reader.peek()? {
+            Event::StartElement(element) => {
+                let name = element.resolved_name();
+                let variant = match self
+                    .variants
+                    .iter()
+                    .find(|variant| name.matches(variant, config))
+                {
+                    Some(variant) => variant.to_string(),
+                    None => name.canonical(&config.namespaces),
+                };
+                (variant, Some(element.qname()))
+            }
+            Event::Text(_) => (config.markers.text.clone(), None),
             event => {
                 return Err(Error::Unexpected {
                     expected: "start of element",
@@ -40,21 +53,30 @@ impl<'de, 'a, R: Read> serde::de::EnumAccess<'de> for EnumAccess<'a, R> {
                 });
             }
         };
-        trace!("enum variant {element_name}");
-        let name = seed.deserialize::<StrDeserializer<Self::Error>>(
-            element_name.as_str().into_deserializer(),
-        )?;
+        trace!("enum variant {variant}");
+        let name = seed
+            .deserialize::<StrDeserializer<Self::Error>>(variant.as_str().into_deserializer())
+            .map_err(|error| error.at(position).within(step(element_name.as_deref())))?;
         Ok((name, VariantAccess::new(self.reader, element_name)))
     }
 }
 
-pub struct VariantAccess<'a, R: Read> {
-    reader: ChildReader<'a, R>,
-    element_name: String,
+/// Step to the element of a variant, or to its text
+fn step(element_name: Option<&str>) -> Step {
+    match element_name {
+        Some(element_name) => Step::element(element_name),
+        None => Step::Text,
+    }
+}
+
+pub struct VariantAccess<'a, R: Read, B> {
+    reader: ChildReader<'a, R, B>,
+    /// Name of the element of the variant, or none if the variant is text
+    element_name: Option<String>,
 }
 
-impl<'a, R: Read> VariantAccess<'a, R> {
-    pub fn new(reader: ChildReader<'a, R>, element_name: String) -> Self {
+impl<'a, R: Read, B> VariantAccess<'a, R, B> {
+    pub fn new(reader: ChildReader<'a, R, B>, element_name: Option<String>) -> Self {
         Self {
             reader,
             element_name,
@@ -62,14 +84,27 @@ impl<'a, R: Read> VariantAccess<'a, R> {
     }
 }
 
-impl<'de, R: Read> serde::de::VariantAccess<'de> for VariantAccess<'_, R> {
+impl<'de, R: Read, B: Input<'de, R>> serde::de::VariantAccess<'de> for VariantAccess<'_, R, B> {
     type Error = Error;
 
     fn unit_variant(mut self) -> Result<()> {
         trace!("unit variant");
-        self.reader.start_element()?;
-        self.reader.end_element()?;
-        Ok(())
+        let position = self.reader.position()?;
+        let mark = self
+            .reader
+            .collector
+            .enter(|| step(self.element_name.as_deref()), position);
+        let value = self
+            .reader
+            .start_element_without_attributes()
+            .and_then(|()| self.reader.end_element())
+            .map_err(|error| {
+                error
+                    .at(position)
+                    .within(step(self.element_name.as_deref()))
+            });
+        self.reader.collector.leave(mark);
+        value
     }
 
     fn newtype_variant_seed<T>(mut self, seed: T) -> Result<T::Value>
@@ -77,13 +112,23 @@ impl<'de, R: Read> serde::de::VariantAccess<'de> for VariantAccess<'_, R> {
         T: serde::de::DeserializeSeed<'de>,
     {
         trace!("newtype variant");
-        if self.element_name == TEXT {
-            seed.deserialize(PlainTextDeserializer::new(&self.reader.chars()?))
-        } else {
-            seed.deserialize(ChildDeserializer::new_with_element_name(
-                self.reader,
-                self.element_name,
-            ))
+        let position = self.reader.position()?;
+        match self.element_name {
+            None => {
+                let text = self.reader.text()?;
+                seed.deserialize(
+                    PlainTextDeserializer::new(&text.value, self.reader.config)
+                        .borrowing(self.reader.lend(&text.span)),
+                )
+                .map_err(|error| error.at(position).within(Step::Text))
+            }
+            // The element is stepped into by whatever deserializes it
+            Some(element_name) => seed
+                .deserialize(ChildDeserializer::new_with_element_name(
+                    self.reader.child(),
+                    element_name,
+                ))
+                .map_err(|error| error.at(position)),
         }
     }
 
@@ -92,9 +137,22 @@ impl<'de, R: Read> serde::de::VariantAccess<'de> for VariantAccess<'_, R> {
         V: serde::de::Visitor<'de>,
     {
         trace!("tuple variant");
-        self.reader.start_element()?;
-        let text = self.reader.chars()?;
-        let value = visitor.visit_seq(PlainTextDeserializer::new(&text))?;
+        let position = self.reader.position()?;
+        let mark = self
+            .reader
+            .collector
+            .enter(|| step(self.element_name.as_deref()), position);
+        let value = self
+            .reader
+            .start_element_without_attributes()
+            .and_then(|()| visitor.visit_seq(TupleAccess::new(self.reader.child())))
+            .map_err(|error| {
+                error
+                    .at(position)
+                    .within(step(self.element_name.as_deref()))
+            });
+        self.reader.collector.leave(mark);
+        let value = value?;
         self.reader.end_element()?;
         Ok(value)
     }
@@ -104,12 +162,25 @@ impl<'de, R: Read> serde::de::VariantAccess<'de> for VariantAccess<'_, R> {
         V: serde::de::Visitor<'de>,
     {
         trace!("struct variant");
+        let position = self.reader.position()?;
         let element = self.reader.start_element()?;
-        let value = visitor.visit_map(MapAccess::new_struct(
-            self.reader.child(),
-            element.attributes,
-            fields,
-        ))?;
+        let mark = self
+            .reader
+            .collector
+            .enter(|| step(self.element_name.as_deref()), position);
+        let value = visitor
+            .visit_map(MapAccess::new_struct(
+                self.reader.child(),
+                element.attributes,
+                fields,
+            ))
+            .map_err(|error| {
+                error
+                    .at(position)
+                    .within(step(self.element_name.as_deref()))
+            });
+        self.reader.collector.leave(mark);
+        let value = value?;
         self.reader.end_element()?;
         Ok(value)
     }
@@ -1,5 +1,102 @@
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Where something appears in a document, counting lines and columns from 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: u64,
+    pub column: u64,
+}
+
+impl From<xml::common::TextPosition> for Position {
+    fn from(value: xml::common::TextPosition) -> Self {
+        Self {
+            line: value.row + 1,
+            column: value.column + 1,
+        }
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// A step from an element to one of its children, attributes or text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// A child element, with its 1-based index among its siblings of the same name when it is an
+    /// item of a sequence
+    Element { name: String, index: Option<usize> },
+    /// An attribute
+    Attribute(String),
+    /// The text content
+    Text,
+}
+
+impl Step {
+    pub(crate) fn element(name: impl Into<String>) -> Self {
+        Self::Element {
+            name: name.into(),
+            index: None,
+        }
+    }
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Element {
+                name,
+                index: Some(index),
+            } => write!(f, "{name}[{index}]"),
+            Step::Element { name, index: None } => write!(f, "{name}"),
+            Step::Attribute(name) => write!(f, "@{name}"),
+            Step::Text => write!(f, "text()"),
+        }
+    }
+}
+
+/// Where something appears in a document, as steps from the document root, displayed with an
+/// XPath-like syntax such as `/project/dependencies/dependency[3]/@scope`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Path {
+    steps: Vec<Step>,
+}
+
+impl Path {
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Prepend a step.
+    pub(crate) fn push_front(&mut self, step: Step) {
+        self.steps.insert(0, step);
+    }
+}
+
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            write!(f, "/{step}")?;
+        }
+        Ok(())
+    }
+}
+
+fn location(path: &Path, position: &Option<Position>) -> String {
+    match (path.is_empty(), position) {
+        (false, Some(position)) => format!("{path}, {position}"),
+        (false, None) => path.to_string(),
+        (true, Some(position)) => position.to_string(),
+        (true, None) => "unknown location".to_string(),
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Unsupported operation {0}")]
@@ -14,8 +111,16 @@ pub enum Error {
     )]
     AttributesMustComeBeforeElements {
         element_name: String,
-        attribute_name: &'static str,
+        attribute_name: String,
     },
+    #[error("Unknown content: {0} is not deserialized")]
+    UnknownContent(String),
+    #[error("Expected root element '{expected}' but got '{found}'")]
+    UnexpectedRoot { expected: String, found: String },
+    #[error("Element '{found}' is out of order, it must come before '{before}'")]
+    UnexpectedOrder { found: String, before: String },
+    #[error("Prefix '{prefix}' in '{name}' is not bound to a namespace. Configure it with SerdeXml::namespace.")]
+    UnboundPrefix { prefix: String, name: String },
     #[error("Custom: {0}")]
     Custom(String),
     #[error("Reader: {0}")]
@@ -30,6 +135,83 @@ pub enum Error {
     ParseInt(#[from] std::num::ParseIntError),
     #[error("Parse float: {0}")]
     ParseFloat(#[from] std::num::ParseFloatError),
+    #[error("{source} (at {})", location(.path, .position))]
+    Located {
+        path: Path,
+        position: Option<Position>,
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Where the error was raised in the document, if known
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Located {
+                position, source, ..
+            } => position.or_else(|| source.position()),
+            Self::Reader(error) => Some(xml::common::Position::position(error).into()),
+            _ => None,
+        }
+    }
+
+    /// Path from the document root to where the error was raised, if known
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Located { path, .. } if !path.is_empty() => Some(path),
+            _ => None,
+        }
+    }
+
+    /// The error, without its location
+    pub fn inner(&self) -> &Error {
+        match self {
+            Self::Located { source, .. } => source,
+            error => error,
+        }
+    }
+
+    /// Attach a position to the error, unless it already knows where it was raised.
+    pub(crate) fn at(self, position: Option<Position>) -> Self {
+        if position.is_none() || self.position().is_some() {
+            return self;
+        }
+        match self {
+            Self::Located { path, source, .. } => Self::Located {
+                path,
+                position,
+                source,
+            },
+            error => Self::Located {
+                path: Path::default(),
+                position,
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Prepend a step to the path of the error, as it propagates to the parent element.
+    pub(crate) fn within(self, step: Step) -> Self {
+        match self {
+            Self::Located {
+                mut path,
+                position,
+                source,
+            } => {
+                path.push_front(step);
+                Self::Located {
+                    path,
+                    position,
+                    source,
+                }
+            }
+            error => Self::Located {
+                path: Path { steps: vec![step] },
+                position: None,
+                source: Box::new(error),
+            },
+        }
+    }
 }
 
 impl serde::de::Error for Error {
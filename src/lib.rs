@@ -8,8 +8,10 @@ pub mod ser;
 mod test;
 
 pub use crate::config::SerdeXml;
-pub use crate::de::{from_reader, from_str, Deserializer};
-pub use crate::error::Error;
+#[cfg(feature = "async")]
+pub use crate::de::from_async_reader;
+pub use crate::de::{from_reader, from_slice, from_str, Deserializer, Ignored, IgnoredKind};
+pub use crate::error::{Error, Path, Position, Step};
 pub use crate::ser::{to_string, to_writer, Serializer};
 
 #[doc = include_str!("../README.md")]
@@ -61,7 +61,8 @@ impl<'a, W: Write> serde::ser::Serializer for ChildSerializer<'a, W> {
     type SerializeStructVariant = StructSerializer<'a, W>;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
-        self.serialize_str(&v.to_string())
+        let text = self.writer.config.booleans.format(v).to_string();
+        self.serialize_str(&text)
     }
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
@@ -117,13 +118,16 @@ impl<'a, W: Write> serde::ser::Serializer for ChildSerializer<'a, W> {
 
     fn serialize_bytes(mut self, v: &[u8]) -> Result<Self::Ok> {
         self.maybe_start_element()?;
-        self.writer.characters(String::from_utf8(v.to_vec())?)?;
+        let text = self.writer.config.binary_encoding.encode(v)?;
+        self.writer.characters(text)?;
         self.maybe_end_element()?;
         Ok(())
     }
 
     fn serialize_none(self) -> Result<Self::Ok> {
-        if self.newtype_struct {
+        if let (Some(element_name), true) = (&self.element_name, self.writer.config.none_as_nil) {
+            self.writer.nil_element(element_name)
+        } else if self.newtype_struct {
             self.serialize_unit()
         } else {
             Ok(())
@@ -218,9 +222,8 @@ impl<'a, W: Write> serde::ser::Serializer for ChildSerializer<'a, W> {
         Ok(TupleSerializer::new(self.writer, should_end_element))
     }
 
-    fn serialize_map(mut self, _len: Option<usize>) -> Result<Self::SerializeMap> {
-        let should_end_element = self.maybe_start_element()?;
-        Ok(MapSerializer::new(self.writer, should_end_element))
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(MapSerializer::new(self.writer, self.element_name))
     }
 
     fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
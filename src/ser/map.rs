@@ -3,10 +3,7 @@ use super::{
     plain::PlainTextSerializer,
     writer::{Attribute, Writer},
 };
-use crate::{
-    config::{CONTENT, TEXT},
-    error::{Error, Result},
-};
+use crate::error::{Error, Result};
 use std::io::Write;
 
 pub struct StructSerializer<'a, W> {
@@ -76,26 +73,33 @@ impl<W: Write> serde::ser::SerializeStruct for StructSerializer<'_, W> {
         T: ?Sized + serde::Serialize,
     {
         self.ensure_parent_element_written()?;
-        if let Some(name) = key.strip_prefix("@") {
+        if let Some(name) = self.writer.config.markers.attribute_name(key) {
             if self.start_element_written {
                 Err(Error::AttributesMustComeBeforeElements {
                     element_name: self.name.to_string(),
-                    attribute_name: key,
+                    attribute_name: key.to_string(),
                 })
             } else {
-                if let Some(value) = value.serialize(PlainTextSerializer)? {
-                    self.attributes.push(Attribute { name, value });
+                if let Some(value) =
+                    value.serialize(PlainTextSerializer::new(&self.writer.config))?
+                {
+                    self.attributes.push(Attribute {
+                        name: name.to_string(),
+                        value,
+                    });
                 }
                 Ok(())
             }
         } else {
             self.ensure_start_element_written()?;
-            if key == TEXT {
-                if let Some(value) = value.serialize(PlainTextSerializer)? {
+            if self.writer.config.markers.is_content(key) {
+                value.serialize(ChildSerializer::new(self.writer, None))?;
+            } else if key == self.writer.config.markers.text {
+                if let Some(value) =
+                    value.serialize(PlainTextSerializer::new(&self.writer.config))?
+                {
                     self.writer.characters(value)?;
                 }
-            } else if key == CONTENT {
-                value.serialize(ChildSerializer::new(self.writer, None))?;
             } else {
                 value.serialize(ChildSerializer::new(self.writer, Some(key.to_string())))?;
             }
@@ -132,17 +136,34 @@ impl<W: Write> serde::ser::SerializeStructVariant for StructSerializer<'_, W> {
 
 pub struct MapSerializer<'a, W> {
     writer: &'a mut Writer<W>,
-    element_name: String,
-    should_end_element: bool,
+    name: Option<String>,
+    key: String,
+    attributes: Vec<Attribute>,
+    start_element_written: bool,
 }
 
 impl<'a, W> MapSerializer<'a, W> {
-    pub fn new(writer: &'a mut Writer<W>, should_end_element: bool) -> Self {
+    pub fn new(writer: &'a mut Writer<W>, name: Option<String>) -> Self {
         Self {
             writer,
-            element_name: "".to_string(),
-            should_end_element,
+            name,
+            key: "".to_string(),
+            attributes: Vec::new(),
+            start_element_written: false,
+        }
+    }
+}
+
+impl<W: Write> MapSerializer<'_, W> {
+    fn ensure_start_element_written(&mut self) -> Result<()> {
+        if !self.start_element_written {
+            if let Some(name) = &self.name {
+                self.writer
+                    .start_element_with_attributes(name, &self.attributes)?;
+            }
+            self.start_element_written = true;
         }
+        Ok(())
     }
 }
 
@@ -154,8 +175,8 @@ impl<W: Write> serde::ser::SerializeMap for MapSerializer<'_, W> {
     where
         T: ?Sized + serde::Serialize,
     {
-        self.element_name = key
-            .serialize(PlainTextSerializer)?
+        self.key = key
+            .serialize(PlainTextSerializer::new(&self.writer.config))?
             .ok_or(Error::Unexpected {
                 expected: "key",
                 but_got: "Option::None".to_string(),
@@ -167,19 +188,41 @@ impl<W: Write> serde::ser::SerializeMap for MapSerializer<'_, W> {
     where
         T: ?Sized + serde::Serialize,
     {
-        let element_name = std::mem::replace(&mut self.element_name, "".to_string());
-        if element_name == TEXT {
-            if let Some(text) = value.serialize(PlainTextSerializer)? {
+        let key = std::mem::replace(&mut self.key, "".to_string());
+        if let Some(name) = self.writer.config.markers.attribute_name(&key) {
+            if self.start_element_written {
+                return Err(Error::AttributesMustComeBeforeElements {
+                    element_name: self.name.clone().unwrap_or_default(),
+                    attribute_name: key,
+                });
+            }
+            if self.name.is_none() {
+                return Err(Error::Unsupported("attribute outside of an element"));
+            }
+            if let Some(value) = value.serialize(PlainTextSerializer::new(&self.writer.config))? {
+                self.attributes.push(Attribute {
+                    name: name.to_string(),
+                    value,
+                });
+            }
+            return Ok(());
+        }
+        self.ensure_start_element_written()?;
+        if self.writer.config.markers.is_content(&key) {
+            value.serialize(ChildSerializer::new(self.writer, None))?;
+        } else if key == self.writer.config.markers.text {
+            if let Some(text) = value.serialize(PlainTextSerializer::new(&self.writer.config))? {
                 self.writer.characters(text)?;
             }
         } else {
-            value.serialize(ChildSerializer::new(self.writer, Some(element_name)))?;
+            value.serialize(ChildSerializer::new(self.writer, Some(key)))?;
         }
         Ok(())
     }
 
-    fn end(self) -> Result<Self::Ok> {
-        if self.should_end_element {
+    fn end(mut self) -> Result<Self::Ok> {
+        self.ensure_start_element_written()?;
+        if self.name.is_some() {
             self.writer.end_element()?;
         }
         Ok(())
@@ -5,9 +5,14 @@ mod seq;
 mod tuple;
 mod writer;
 
-use self::{child::ChildSerializer, map::StructSerializer, tuple::TupleSerializer};
+use self::{
+    child::ChildSerializer,
+    map::{MapSerializer, StructSerializer},
+    seq::SequenceSerializer,
+    tuple::TupleSerializer,
+};
 use crate::{
-    config::{Namespaces, SerdeXml},
+    config::{SerdeXml, ROOT_ITEM},
     error::{Error, Result},
 };
 use log::trace;
@@ -78,7 +83,7 @@ pub struct Serializer<W> {
 impl<W: Write> Serializer<W> {
     pub fn new(writer: EventWriter<W>) -> Self {
         Self {
-            writer: Writer::new(writer, Namespaces::default()),
+            writer: Writer::new(writer, SerdeXml::default()),
         }
     }
 
@@ -86,83 +91,113 @@ impl<W: Write> Serializer<W> {
         Self::from_config(SerdeXml::default(), writer)
     }
 
+    /// Give the root element a name chosen at runtime, instead of the name of the serialized
+    /// type.
+    pub fn with_root_element_name<S: ToString>(mut self, name: S) -> Self {
+        self.writer.root_element_name = Some(name.to_string());
+        self
+    }
+
     pub fn into_inner(self) -> W {
         self.writer.into_inner()
     }
 
     pub(crate) fn from_config(config: SerdeXml, sink: W) -> Self {
         Self {
-            writer: Writer::new(config.emitter.create_writer(sink), config.namespaces),
+            writer: Writer::new(config.emitter.clone().create_writer(sink), config),
         }
     }
+
+    /// The configured root element name, for values that have no name of their own
+    fn root_element_name(&self, unsupported: &'static str) -> Result<String> {
+        self.writer
+            .root_element_name
+            .as_ref()
+            .or(self.writer.config.root_element_name.as_ref())
+            .cloned()
+            .ok_or(Error::Unsupported(unsupported))
+    }
+
+    /// Serialize a value with no name of its own as the root element
+    fn root_child(&mut self, unsupported: &'static str) -> Result<ChildSerializer<'_, W>> {
+        let name = self.root_element_name(unsupported)?;
+        Ok(ChildSerializer::new(&mut self.writer, Some(name)))
+    }
 }
 
 impl<'a, W: Write> serde::ser::Serializer for &'a mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    type SerializeSeq = serde::ser::Impossible<Self::Ok, Self::Error>;
-    type SerializeTuple = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeSeq = SequenceSerializer<'a, W>;
+    type SerializeTuple = TupleSerializer<'a, W>;
     type SerializeTupleStruct = TupleSerializer<'a, W>;
     type SerializeTupleVariant = TupleSerializer<'a, W>;
-    type SerializeMap = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = MapSerializer<'a, W>;
     type SerializeStruct = StructSerializer<'a, W>;
     type SerializeStructVariant = StructSerializer<'a, W>;
 
-    fn serialize_bool(self, _v: bool) -> Result<Self::Ok> {
-        Err(Error::Unsupported("bool in document root"))
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
+        self.root_child("bool in document root")?.serialize_bool(v)
     }
 
-    fn serialize_i8(self, _v: i8) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?.serialize_i8(v)
     }
 
-    fn serialize_i16(self, _v: i16) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?
+            .serialize_i16(v)
     }
 
-    fn serialize_i32(self, _v: i32) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?
+            .serialize_i32(v)
     }
 
-    fn serialize_i64(self, _v: i64) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?
+            .serialize_i64(v)
     }
 
-    fn serialize_u8(self, _v: u8) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?.serialize_u8(v)
     }
 
-    fn serialize_u16(self, _v: u16) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?
+            .serialize_u16(v)
     }
 
-    fn serialize_u32(self, _v: u32) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?
+            .serialize_u32(v)
     }
 
-    fn serialize_u64(self, _v: u64) -> Result<Self::Ok> {
-        Err(Error::Unsupported("integer in document root"))
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        self.root_child("integer in document root")?
+            .serialize_u64(v)
     }
 
-    fn serialize_f32(self, _v: f32) -> Result<Self::Ok> {
-        Err(Error::Unsupported("float in document root"))
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
+        self.root_child("float in document root")?.serialize_f32(v)
     }
 
-    fn serialize_f64(self, _v: f64) -> Result<Self::Ok> {
-        Err(Error::Unsupported("float in document root"))
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
+        self.root_child("float in document root")?.serialize_f64(v)
     }
 
-    fn serialize_char(self, _v: char) -> Result<Self::Ok> {
-        Err(Error::Unsupported("char in document root"))
+    fn serialize_char(self, v: char) -> Result<Self::Ok> {
+        self.root_child("char in document root")?.serialize_char(v)
     }
 
-    fn serialize_str(self, _v: &str) -> Result<Self::Ok> {
-        Err(Error::Unsupported("string in document root"))
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        self.root_child("string in document root")?.serialize_str(v)
     }
 
-    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok> {
-        Err(Error::Unsupported("bytes"))
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        self.root_child("bytes in document root")?
+            .serialize_bytes(v)
     }
 
     fn serialize_none(self) -> Result<Self::Ok> {
@@ -179,7 +214,7 @@ impl<'a, W: Write> serde::ser::Serializer for &'a mut Serializer<W> {
     }
 
     fn serialize_unit(self) -> Result<Self::Ok> {
-        Err(Error::Unsupported("unit in document root"))
+        self.root_child("unit in document root")?.serialize_unit()
     }
 
     fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok> {
@@ -236,11 +271,20 @@ impl<'a, W: Write> serde::ser::Serializer for &'a mut Serializer<W> {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
-        Err(Error::Unsupported("sequence in document root"))
+        let name = self.root_element_name("sequence in document root")?;
+        trace!("sequence '{name}'");
+        self.writer.start_element(name)?;
+        Ok(SequenceSerializer::new_in_element(
+            &mut self.writer,
+            ROOT_ITEM.to_string(),
+        ))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
-        Err(Error::Unsupported("tuple in document root"))
+        let name = self.root_element_name("tuple in document root")?;
+        trace!("tuple '{name}'");
+        self.writer.start_element(name)?;
+        Ok(TupleSerializer::new(&mut self.writer, true))
     }
 
     fn serialize_tuple_struct(
@@ -267,7 +311,11 @@ impl<'a, W: Write> serde::ser::Serializer for &'a mut Serializer<W> {
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
-        Err(Error::Unsupported("map in document root"))
+        let name = self.root_element_name(
+            "map or struct with flattened fields in document root. Configure a root element name with SerdeXml::root_element_name.",
+        )?;
+        trace!("map '{name}'");
+        Ok(MapSerializer::new(&mut self.writer, Some(name)))
     }
 
     fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
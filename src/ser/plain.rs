@@ -1,22 +1,33 @@
-use crate::error::{Error, Result};
+use crate::{
+    config::SerdeXml,
+    error::{Error, Result},
+};
 use serde::Serializer;
 
-pub struct PlainTextSerializer;
+pub struct PlainTextSerializer<'a> {
+    config: &'a SerdeXml,
+}
+
+impl<'a> PlainTextSerializer<'a> {
+    pub fn new(config: &'a SerdeXml) -> Self {
+        Self { config }
+    }
+}
 
-impl Serializer for PlainTextSerializer {
+impl<'a> Serializer for PlainTextSerializer<'a> {
     type Ok = Option<String>;
     type Error = Error;
 
-    type SerializeSeq = PlainTextSeqSerializer;
-    type SerializeTuple = PlainTextSeqSerializer;
-    type SerializeTupleStruct = PlainTextSeqSerializer;
+    type SerializeSeq = PlainTextSeqSerializer<'a>;
+    type SerializeTuple = PlainTextSeqSerializer<'a>;
+    type SerializeTupleStruct = PlainTextSeqSerializer<'a>;
     type SerializeTupleVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeMap = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeStruct = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeStructVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
-        Ok(Some(v.to_string()))
+        Ok(Some(self.config.booleans.format(v).to_string()))
     }
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
@@ -68,8 +79,7 @@ impl Serializer for PlainTextSerializer {
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
-        let s = String::from_utf8(v.to_vec())?;
-        Ok(Some(s))
+        Ok(Some(self.config.binary_encoding.encode(v)?))
     }
 
     fn serialize_none(self) -> Result<Self::Ok> {
@@ -121,11 +131,11 @@ impl Serializer for PlainTextSerializer {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
-        Ok(PlainTextSeqSerializer::new())
+        Ok(PlainTextSeqSerializer::new(self.config))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
-        Ok(PlainTextSeqSerializer::new())
+        Ok(PlainTextSeqSerializer::new(self.config))
     }
 
     fn serialize_tuple_struct(
@@ -133,7 +143,7 @@ impl Serializer for PlainTextSerializer {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        Ok(PlainTextSeqSerializer::new())
+        Ok(PlainTextSeqSerializer::new(self.config))
     }
 
     fn serialize_tuple_variant(
@@ -165,17 +175,21 @@ impl Serializer for PlainTextSerializer {
     }
 }
 
-pub struct PlainTextSeqSerializer {
+pub struct PlainTextSeqSerializer<'a> {
     buffer: Vec<String>,
+    config: &'a SerdeXml,
 }
 
-impl PlainTextSeqSerializer {
-    fn new() -> Self {
-        Self { buffer: Vec::new() }
+impl<'a> PlainTextSeqSerializer<'a> {
+    fn new(config: &'a SerdeXml) -> Self {
+        Self {
+            buffer: Vec::new(),
+            config,
+        }
     }
 }
 
-impl serde::ser::SerializeSeq for PlainTextSeqSerializer {
+impl serde::ser::SerializeSeq for PlainTextSeqSerializer<'_> {
     type Ok = Option<String>;
     type Error = Error;
 
@@ -183,7 +197,7 @@ impl serde::ser::SerializeSeq for PlainTextSeqSerializer {
     where
         T: ?Sized + serde::Serialize,
     {
-        if let Some(value) = value.serialize(PlainTextSerializer)? {
+        if let Some(value) = value.serialize(PlainTextSerializer::new(self.config))? {
             self.buffer.push(value);
         }
         Ok(())
@@ -194,7 +208,7 @@ impl serde::ser::SerializeSeq for PlainTextSeqSerializer {
     }
 }
 
-impl serde::ser::SerializeTuple for PlainTextSeqSerializer {
+impl serde::ser::SerializeTuple for PlainTextSeqSerializer<'_> {
     type Ok = Option<String>;
     type Error = Error;
 
@@ -210,7 +224,7 @@ impl serde::ser::SerializeTuple for PlainTextSeqSerializer {
     }
 }
 
-impl serde::ser::SerializeTupleStruct for PlainTextSeqSerializer {
+impl serde::ser::SerializeTupleStruct for PlainTextSeqSerializer<'_> {
     type Ok = Option<String>;
     type Error = Error;
 
@@ -225,3 +239,262 @@ impl serde::ser::SerializeTupleStruct for PlainTextSeqSerializer {
         <Self as serde::ser::SerializeSeq>::end(self)
     }
 }
+
+macro_rules! plain_text {
+    ($($serialize:ident($type:ty)),* $(,)?) => {
+        $(
+            fn $serialize(self, _v: $type) -> Result<Self::Ok> {
+                Ok(true)
+            }
+        )*
+    };
+}
+
+/// Tells whether a value serializes to plain text, as with [PlainTextSerializer], without
+/// serializing it
+pub struct IsPlainText;
+
+impl Serializer for IsPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    type SerializeSeq = AllPlainText;
+    type SerializeTuple = AllPlainText;
+    type SerializeTupleStruct = AllPlainText;
+    type SerializeTupleVariant = NotPlainText;
+    type SerializeMap = NotPlainText;
+    type SerializeStruct = NotPlainText;
+    type SerializeStructVariant = NotPlainText;
+
+    plain_text!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    );
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(true)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Ok(true)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok> {
+        Ok(true)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        Ok(false)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(AllPlainText(true))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Ok(AllPlainText(true))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Ok(AllPlainText(true))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(NotPlainText)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(NotPlainText)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(NotPlainText)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(NotPlainText)
+    }
+}
+
+/// A sequence is plain text if all its items are
+pub struct AllPlainText(bool);
+
+impl serde::ser::SerializeSeq for AllPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.0 = self.0 && value.serialize(IsPlainText)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.0)
+    }
+}
+
+impl serde::ser::SerializeTuple for AllPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        <Self as serde::ser::SerializeSeq>::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        <Self as serde::ser::SerializeSeq>::end(self)
+    }
+}
+
+impl serde::ser::SerializeTupleStruct for AllPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        <Self as serde::ser::SerializeSeq>::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        <Self as serde::ser::SerializeSeq>::end(self)
+    }
+}
+
+/// Maps, structs and variants with fields are never plain text, whatever their fields
+pub struct NotPlainText;
+
+impl serde::ser::SerializeTupleVariant for NotPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(false)
+    }
+}
+
+impl serde::ser::SerializeMap for NotPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, _key: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, _value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(false)
+    }
+}
+
+impl serde::ser::SerializeStruct for NotPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(false)
+    }
+}
+
+impl serde::ser::SerializeStructVariant for NotPlainText {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> Result<()>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(false)
+    }
+}
@@ -7,6 +7,7 @@ use super::{child::ChildSerializer, writer::Writer};
 pub struct SequenceSerializer<'a, W> {
     writer: &'a mut Writer<W>,
     element_name: Option<String>,
+    should_end_element: bool,
 }
 
 impl<'a, W: Write> SequenceSerializer<'a, W> {
@@ -14,6 +15,16 @@ impl<'a, W: Write> SequenceSerializer<'a, W> {
         Self {
             writer,
             element_name,
+            should_end_element: false,
+        }
+    }
+
+    /// A sequence inside an element that is already started, and that ends with it
+    pub fn new_in_element(writer: &'a mut Writer<W>, element_name: String) -> Self {
+        Self {
+            writer,
+            element_name: Some(element_name),
+            should_end_element: true,
         }
     }
 }
@@ -30,6 +41,9 @@ impl<W: Write> SerializeSeq for SequenceSerializer<'_, W> {
     }
 
     fn end(self) -> Result<Self::Ok> {
+        if self.should_end_element {
+            self.writer.end_element()?;
+        }
         Ok(())
     }
 }
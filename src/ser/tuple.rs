@@ -1,12 +1,22 @@
-use super::{plain::PlainTextSerializer, writer::Writer};
+use super::{
+    child::ChildSerializer,
+    plain::{IsPlainText, PlainTextSerializer},
+    writer::Writer,
+};
 use crate::error::{Error, Result};
 use serde::ser::{SerializeTuple, SerializeTupleStruct, SerializeTupleVariant};
 use std::io::Write;
 
+/// Serializes a tuple by position: runs of primitive members become whitespace-separated text,
+/// and other members, such as structs and enums, become consecutive child elements. Next to
+/// elements, the text of a primitive member must not be empty nor contain whitespace, as it
+/// could not be read back.
 pub struct TupleSerializer<'a, W> {
     writer: &'a mut Writer<W>,
     buffer: Vec<String>,
     should_end_element: bool,
+    /// Whether any member was written as an element
+    has_elements: bool,
 }
 
 impl<'a, W> TupleSerializer<'a, W> {
@@ -15,10 +25,33 @@ impl<'a, W> TupleSerializer<'a, W> {
             writer,
             buffer: Vec::new(),
             should_end_element,
+            has_elements: false,
         }
     }
 }
 
+impl<W: Write> TupleSerializer<'_, W> {
+    /// Write the buffered primitive members as text. An all-primitive tuple is always written as
+    /// text, even when empty.
+    fn flush(&mut self) -> Result<()> {
+        if self.has_elements
+            && self
+                .buffer
+                .iter()
+                .any(|text| text.is_empty() || text.contains(char::is_whitespace))
+        {
+            return Err(Error::Unsupported(
+                "empty text or text with whitespace next to elements in a tuple",
+            ));
+        }
+        if !self.buffer.is_empty() || !self.has_elements {
+            self.writer.characters(self.buffer.join(" ").as_str())?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+}
+
 impl<W: Write> SerializeTuple for TupleSerializer<'_, W> {
     type Ok = ();
     type Error = Error;
@@ -27,14 +60,20 @@ impl<W: Write> SerializeTuple for TupleSerializer<'_, W> {
     where
         T: ?Sized + serde::Serialize,
     {
-        if let Some(text) = value.serialize(PlainTextSerializer)? {
-            self.buffer.push(text);
+        if value.serialize(IsPlainText)? {
+            if let Some(text) = value.serialize(PlainTextSerializer::new(&self.writer.config))? {
+                self.buffer.push(text);
+            }
+        } else {
+            self.has_elements = true;
+            self.flush()?;
+            value.serialize(ChildSerializer::new(self.writer, None))?;
         }
         Ok(())
     }
 
-    fn end(self) -> Result<Self::Ok> {
-        self.writer.characters(self.buffer.join(" ").as_str())?;
+    fn end(mut self) -> Result<Self::Ok> {
+        self.flush()?;
         if self.should_end_element {
             self.writer.end_element()?;
         }
@@ -69,8 +108,8 @@ impl<W: Write> SerializeTupleVariant for TupleSerializer<'_, W> {
         <Self as SerializeTuple>::serialize_element(self, value)
     }
 
-    fn end(self) -> Result<Self::Ok> {
-        self.writer.characters(self.buffer.join(" ").as_str())?;
+    fn end(mut self) -> Result<Self::Ok> {
+        self.flush()?;
         self.writer.end_element()?;
         if self.should_end_element {
             self.writer.end_element()?;
@@ -1,24 +1,80 @@
-use crate::{config::Namespaces, error::Result};
+use crate::{
+    config::{SerdeXml, XSI_NAMESPACE},
+    error::{Error, Result},
+};
 use log::trace;
 use std::io::Write;
-use xml::{writer::XmlEvent, EventWriter};
+use xml::{
+    namespace::NS_NO_PREFIX,
+    writer::{events::StartElementBuilder, XmlEvent},
+    EventWriter,
+};
 
 #[derive(Debug, PartialEq)]
 pub struct Attribute {
-    pub name: &'static str,
+    pub name: String,
     pub value: String,
 }
 
 pub struct Writer<W> {
     xml_writer: EventWriter<W>,
-    namespaces: Namespaces,
+    pub config: SerdeXml,
+    /// Name given at runtime to the root element, instead of the name of the serialized type
+    pub root_element_name: Option<String>,
+    /// Number of elements that are open
+    depth: usize,
 }
 
 impl<W> Writer<W> {
-    pub fn new(xml_writer: EventWriter<W>, namespaces: Namespaces) -> Self {
+    pub fn new(xml_writer: EventWriter<W>, config: SerdeXml) -> Self {
         Self {
             xml_writer,
-            namespaces,
+            config,
+            root_element_name: None,
+            depth: 0,
+        }
+    }
+
+    /// Replace a name in Clark notation, `{uri}local`, with the prefix configured for its
+    /// namespace, and check that the prefix of any other name is configured
+    fn resolve_name(&self, name: &str) -> Result<String> {
+        let name = match &self.root_element_name {
+            Some(root_element_name) if self.depth == 0 => root_element_name,
+            _ => name,
+        };
+        let Some((uri, local)) = name.strip_prefix('{').and_then(|name| name.split_once('}'))
+        else {
+            return match name.split_once(':') {
+                Some((prefix, _))
+                    if !matches!(prefix, "xml" | "xmlns")
+                        && self.config.namespaces.get(prefix).is_none() =>
+                {
+                    Err(Error::UnboundPrefix {
+                        prefix: prefix.to_string(),
+                        name: name.to_string(),
+                    })
+                }
+                _ => Ok(name.to_string()),
+            };
+        };
+        match self.config.namespaces.prefix_for(uri) {
+            Some(NS_NO_PREFIX) => Ok(local.to_string()),
+            Some(prefix) => Ok(format!("{prefix}:{local}")),
+            None => Err(Error::Unsupported("namespace without a configured prefix")),
+        }
+    }
+
+    /// Declare the configured namespaces on the root element only, along with the `xsi`
+    /// namespace of nil elements if they may be written
+    fn declare_namespaces<'a>(&self, element: StartElementBuilder<'a>) -> StartElementBuilder<'a> {
+        if self.depth > 0 {
+            return element;
+        }
+        let element = self.config.namespaces.add_to_start_element(element);
+        if self.config.none_as_nil && self.config.namespaces.get("xsi").is_none() {
+            element.ns("xsi", XSI_NAMESPACE)
+        } else {
+            element
         }
     }
 }
@@ -33,21 +89,37 @@ impl<W: Write> Writer<W> {
         name: S,
         attributes: &[Attribute],
     ) -> Result<()> {
-        let name = name.as_ref();
+        let name = self.resolve_name(name.as_ref())?;
         trace!("EVENT: start element '{name}'");
-        let mut element = self
-            .namespaces
-            .add_to_start_element(XmlEvent::start_element(name));
-        for attribute in attributes {
-            element = element.attr(attribute.name, &attribute.value);
+        let attribute_names = attributes
+            .iter()
+            .map(|attribute| self.resolve_name(&attribute.name))
+            .collect::<Result<Vec<_>>>()?;
+        let mut element = self.declare_namespaces(XmlEvent::start_element(name.as_str()));
+        for (attribute, attribute_name) in attributes.iter().zip(&attribute_names) {
+            element = element.attr(attribute_name.as_str(), &attribute.value);
         }
         self.xml_writer.write(element)?;
+        self.depth += 1;
         Ok(())
     }
 
+    /// Write an empty element marked as an explicit null with `xsi:nil="true"`
+    pub fn nil_element<S: AsRef<str>>(&mut self, name: S) -> Result<()> {
+        let name = self.resolve_name(name.as_ref())?;
+        trace!("EVENT: nil element '{name}'");
+        let element = self
+            .declare_namespaces(XmlEvent::start_element(name.as_str()))
+            .attr("xsi:nil", "true");
+        self.xml_writer.write(element)?;
+        self.depth += 1;
+        self.end_element()
+    }
+
     pub fn end_element(&mut self) -> Result<()> {
         trace!("EVENT: end element");
         self.xml_writer.write(XmlEvent::end_element())?;
+        self.depth -= 1;
         Ok(())
     }
 
@@ -0,0 +1,59 @@
+use crate::from_str;
+use rstest::rstest;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[rstest]
+#[case::empty(r#"<document />"#, json!(""))]
+#[case::text(r#"<document>abc</document>"#, json!("abc"))]
+#[case::attribute(r#"<document id="123" />"#, json!({"@id": "123"}))]
+#[case::attribute_and_text(r#"<document id="123">abc</document>"#, json!({"@id": "123", "#text": "abc"}))]
+#[case::children(r#"<document><a>1</a><b /></document>"#, json!({"a": "1", "b": ""}))]
+#[case::nested(r#"<document><a><b>1</b></a></document>"#, json!({"a": {"b": "1"}}))]
+#[case::repeated(r#"<document><a>1</a><a>2</a></document>"#, json!({"a": ["1", "2"]}))]
+#[case::overlapping(r#"<document><a>1</a><b>2</b><a>3</a></document>"#, json!({"a": ["1", "3"], "b": "2"}))]
+#[case::mixed(r#"<document>text<a>1</a>more<b />end</document>"#, json!({"#text": ["text", "more", "end"], "a": "1", "b": ""}))]
+#[case::mixed_with_one_text(r#"<document><a>1</a>text</document>"#, json!({"#text": "text", "a": "1"}))]
+#[case::repeated_with_attributes(r#"<document><a id="1" /><a id="2">x</a></document>"#, json!({"a": [{"@id": "1"}, {"@id": "2", "#text": "x"}]}))]
+#[test_log::test]
+fn given_document_when_deserialize_any_then_ok(#[case] text: &str, #[case] expected: Value) {
+    assert_eq!(from_str::<Value>(text).unwrap(), expected);
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "document")]
+struct Document {
+    name: String,
+    extra: Value,
+}
+
+#[rstest]
+#[test_log::test]
+fn given_struct_with_any_field_when_deserialize_then_ok() {
+    let text = r#"<document><name>abc</name><extra kind="x"><item>1</item><item>2</item></extra></document>"#;
+    assert_eq!(
+        from_str::<Document>(text).unwrap(),
+        Document {
+            name: "abc".to_string(),
+            extra: json!({"@kind": "x", "item": ["1", "2"]}),
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "document")]
+struct NewtypeDocument(Value);
+
+#[rstest]
+#[case::text(r#"<document>abc</document>"#, json!("abc"))]
+#[case::children(r#"<document><a>1</a><a>2</a></document>"#, json!({"a": ["1", "2"]}))]
+#[test_log::test]
+fn given_newtype_with_any_content_when_deserialize_then_ok(
+    #[case] text: &str,
+    #[case] expected: Value,
+) {
+    assert_eq!(
+        from_str::<NewtypeDocument>(text).unwrap(),
+        NewtypeDocument(expected)
+    );
+}
@@ -0,0 +1,86 @@
+use crate::{from_async_reader, SerdeXml};
+use futures_core::Stream;
+use rstest::rstest;
+use serde::Deserialize;
+use std::{future::poll_fn, pin::Pin};
+use tokio::io::AsyncWriteExt;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "event")]
+struct Event {
+    #[serde(rename = "@type")]
+    r#type: String,
+    #[serde(rename = "#text")]
+    message: String,
+}
+
+async fn collect<T>(mut stream: impl Stream<Item = T> + Unpin) -> Vec<T> {
+    let mut items = Vec::new();
+    while let Some(item) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+        items.push(item);
+    }
+    items
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn given_document_when_deserialize_from_async_reader_then_ok() {
+    let text: &[u8] = br#"<event type="a">an event</event>"#;
+    assert_eq!(
+        from_async_reader::<Event, _>(text).await.unwrap(),
+        Event {
+            r#type: "a".to_string(),
+            message: "an event".to_string(),
+        }
+    );
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn given_ill_formed_document_when_deserialize_from_async_reader_then_error() {
+    let text: &[u8] = br#"<event type="a">an event</evnt>"#;
+    assert!(from_async_reader::<Event, _>(text).await.is_err());
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn given_records_arriving_over_time_when_streaming_then_each_record_is_yielded() {
+    let (mut writer, reader) = tokio::io::duplex(64);
+    let mut stream = SerdeXml::new().iter_from_async_reader::<Event, _>(reader);
+
+    writer
+        .write_all(br#"<event type="a">first</event>"#)
+        .await
+        .unwrap();
+    let first = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.message, "first");
+
+    writer
+        .write_all(br#"<event type="b">second</event>"#)
+        .await
+        .unwrap();
+    drop(writer);
+    let rest = collect(stream).await;
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest[0].as_ref().unwrap().message, "second");
+}
+
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn given_path_when_streaming_then_selected_elements_are_yielded() {
+    let text: &[u8] =
+        br#"<log><event type="a">first</event><other /><event type="b">second</event></log>"#;
+    let events =
+        collect(SerdeXml::new().iter_path_from_async_reader::<Event, _>("log/event", text))
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+    assert_eq!(
+        events.iter().map(|e| e.r#type.as_str()).collect::<Vec<_>>(),
+        vec!["a", "b"]
+    );
+}
@@ -0,0 +1,145 @@
+use crate::{config::BinaryEncoding, from_slice, from_str, SerdeXml};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "file")]
+struct File {
+    #[serde(rename = "@checksum")]
+    checksum: ByteBuf,
+    #[serde(with = "serde_bytes")]
+    content: Vec<u8>,
+}
+
+fn file() -> File {
+    File {
+        checksum: ByteBuf::from(vec![0xca, 0xfe, 0xba, 0xbe]),
+        content: vec![0x00, 0x01, 0x7f, 0x80, 0xff],
+    }
+}
+
+#[rstest]
+#[case::base64(
+    BinaryEncoding::Base64,
+    r#"<file checksum="yv66vg=="><content>AAF/gP8=</content></file>"#
+)]
+#[case::hex(
+    BinaryEncoding::Hex,
+    r#"<file checksum="CAFEBABE"><content>00017F80FF</content></file>"#
+)]
+#[test_log::test]
+fn given_encoding_when_serializing_then_bytes_are_encoded(
+    #[case] encoding: BinaryEncoding,
+    #[case] expected: &str,
+) {
+    let text = SerdeXml::new()
+        .binary_encoding(encoding)
+        .to_string(&file())
+        .unwrap();
+    assert!(text.ends_with(expected), "{text}");
+}
+
+#[rstest]
+#[case::base64(
+    BinaryEncoding::Base64,
+    r#"<file checksum="yv66vg=="><content>
+        AAF/
+        gP8=
+    </content></file>"#
+)]
+#[case::hex(
+    BinaryEncoding::Hex,
+    r#"<file checksum="cafebabe"><content>00017F80FF</content></file>"#
+)]
+#[case::hex_with_whitespace(
+    BinaryEncoding::Hex,
+    r#"<file checksum=" cafe babe "><content>
+        00017F
+        80FF
+    </content></file>"#
+)]
+#[test_log::test]
+fn given_encoding_when_deserializing_then_bytes_are_decoded(
+    #[case] encoding: BinaryEncoding,
+    #[case] text: &str,
+) {
+    let actual = SerdeXml::new()
+        .binary_encoding(encoding)
+        .from_str::<File>(text)
+        .unwrap();
+    assert_eq!(actual, file());
+}
+
+#[rstest]
+#[case::base64(BinaryEncoding::Base64)]
+#[case::hex(BinaryEncoding::Hex)]
+#[test_log::test]
+fn given_encoding_when_round_tripping_then_same_bytes(#[case] encoding: BinaryEncoding) {
+    let config = SerdeXml::new().binary_encoding(encoding);
+    let text = config.clone().to_string(&file()).unwrap();
+    assert_eq!(config.from_str::<File>(&text).unwrap(), file());
+}
+
+#[rstest]
+#[case::base64(
+    BinaryEncoding::Base64,
+    r#"<file checksum="yv66vg=="><content>not base64!</content></file>"#
+)]
+#[case::hex_odd_length(
+    BinaryEncoding::Hex,
+    r#"<file checksum="CAFEBAB"><content>00</content></file>"#
+)]
+#[case::hex_digit(
+    BinaryEncoding::Hex,
+    r#"<file checksum="CAFEBABE"><content>0G</content></file>"#
+)]
+#[test_log::test]
+fn given_invalid_encoded_bytes_when_deserializing_then_err(
+    #[case] encoding: BinaryEncoding,
+    #[case] text: &str,
+) {
+    let result = SerdeXml::new()
+        .binary_encoding(encoding)
+        .from_str::<File>(text);
+    assert!(result.is_err());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_default_encoding_when_deserializing_then_utf8_bytes() {
+    let text = r#"<file checksum="abc"><content>hello</content></file>"#;
+    assert_eq!(
+        from_str::<File>(text).unwrap(),
+        File {
+            checksum: ByteBuf::from(b"abc".to_vec()),
+            content: b"hello".to_vec(),
+        }
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_default_encoding_when_serializing_invalid_utf8_then_err() {
+    assert!(SerdeXml::new().to_string(&file()).is_err());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_borrowed_bytes_when_deserializing_from_slice_then_borrowed() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Document<'a> {
+        #[serde(rename = "@id")]
+        id: &'a [u8],
+        name: &'a [u8],
+    }
+
+    let text = r#"<document id="x1"><name>hello</name></document>"#;
+    assert_eq!(
+        from_slice::<Document>(text.as_bytes()).unwrap(),
+        Document {
+            id: b"x1",
+            name: b"hello"
+        }
+    );
+}
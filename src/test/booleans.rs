@@ -0,0 +1,131 @@
+use crate::{from_str, SerdeXml};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "checkbox")]
+struct Checkbox {
+    #[serde(rename = "@checked", default)]
+    checked: bool,
+    enabled: bool,
+}
+
+#[rstest]
+#[case::yes_no(
+    r#"<checkbox checked="yes"><enabled>no</enabled></checkbox>"#,
+    true,
+    false
+)]
+#[case::letters(
+    r#"<checkbox checked="n"><enabled>y</enabled></checkbox>"#,
+    false,
+    true
+)]
+#[test_log::test]
+fn given_custom_spellings_when_deserialize_then_ok(
+    #[case] text: &str,
+    #[case] checked: bool,
+    #[case] enabled: bool,
+) {
+    let actual = SerdeXml::new()
+        .boolean_values(&["yes", "y"], &["no", "n"])
+        .from_str::<Checkbox>(text)
+        .unwrap();
+    assert_eq!(actual, Checkbox { checked, enabled });
+}
+
+#[rstest]
+#[test_log::test]
+fn given_custom_spellings_when_deserialize_default_spelling_then_err() {
+    let result = SerdeXml::new()
+        .boolean_values(&["yes"], &["no"])
+        .from_str::<Checkbox>(r#"<checkbox checked="yes"><enabled>true</enabled></checkbox>"#);
+    assert!(result.is_err());
+}
+
+#[rstest]
+#[case::case_sensitive(false, false)]
+#[case::case_insensitive(true, true)]
+#[test_log::test]
+fn given_mixed_case_when_deserialize_then_matches_configured_case_sensitivity(
+    #[case] case_insensitive: bool,
+    #[case] ok: bool,
+) {
+    let result = SerdeXml::new()
+        .case_insensitive_booleans(case_insensitive)
+        .from_str::<Checkbox>(r#"<checkbox checked="True"><enabled>FALSE</enabled></checkbox>"#);
+    assert_eq!(result.is_ok(), ok);
+}
+
+#[rstest]
+#[test_log::test]
+fn given_html_style_attribute_when_deserialize_then_absent_is_false() {
+    let config = SerdeXml::new().boolean_values(&["checked", "true"], &["false"]);
+    assert_eq!(
+        config
+            .clone()
+            .from_str::<Checkbox>(
+                r#"<checkbox checked="checked"><enabled>true</enabled></checkbox>"#
+            )
+            .unwrap(),
+        Checkbox {
+            checked: true,
+            enabled: true
+        }
+    );
+    assert_eq!(
+        config
+            .from_str::<Checkbox>(r#"<checkbox><enabled>true</enabled></checkbox>"#)
+            .unwrap(),
+        Checkbox {
+            checked: false,
+            enabled: true
+        }
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_custom_spellings_when_deserialize_root_then_ok() {
+    let actual = SerdeXml::new()
+        .boolean_values(&["Y"], &["N"])
+        .from_str::<bool>("<flag>Y</flag>")
+        .unwrap();
+    assert!(actual);
+}
+
+#[rstest]
+#[test_log::test]
+fn given_default_config_when_deserialize_then_standard_spellings() {
+    assert_eq!(
+        from_str::<Checkbox>(r#"<checkbox checked="1"><enabled>false</enabled></checkbox>"#)
+            .unwrap(),
+        Checkbox {
+            checked: true,
+            enabled: false
+        }
+    );
+}
+
+#[rstest]
+#[case::yes_no(
+    "yes",
+    "no",
+    r#"<checkbox checked="yes"><enabled>no</enabled></checkbox>"#
+)]
+#[case::letters("Y", "N", r#"<checkbox checked="Y"><enabled>N</enabled></checkbox>"#)]
+#[test_log::test]
+fn given_output_spelling_when_serialize_then_ok(
+    #[case] true_value: &str,
+    #[case] false_value: &str,
+    #[case] expected: &str,
+) {
+    let text = SerdeXml::new()
+        .boolean_output(true_value, false_value)
+        .to_string(&Checkbox {
+            checked: true,
+            enabled: false,
+        })
+        .unwrap();
+    assert!(text.ends_with(expected), "{text}");
+}
@@ -0,0 +1,131 @@
+use crate::{from_reader, from_slice, from_str, SerdeXml};
+use rstest::rstest;
+use serde::Deserialize;
+use std::borrow::Cow;
+
+mod given_borrowed_fields {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item<'a> {
+        #[serde(rename = "@source")]
+        source: &'a str,
+        name: &'a str,
+        #[serde(rename = "tag")]
+        tags: Vec<&'a str>,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_from_slice() {
+        let text = br#"<item source="world.rs"><name>hello</name><tag>a</tag><tag>b</tag></item>"#;
+        assert_eq!(
+            from_slice::<Item>(text).unwrap(),
+            Item {
+                source: "world.rs",
+                name: "hello",
+                tags: vec!["a", "b"],
+            }
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_from_slice_with_config() {
+        let text =
+            br#"<?xml version="1.0"?><item source="world.rs"><name>hello</name><tag>a</tag></item>"#;
+        assert_eq!(
+            SerdeXml::new().from_slice::<Item>(text).unwrap(),
+            Item {
+                source: "world.rs",
+                name: "hello",
+                tags: vec!["a"],
+            }
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_text_is_escaped_then_error() {
+        assert!(from_slice::<Item>(br#"<item source="a"><name>a &amp; b</name></item>"#).is_err());
+    }
+}
+
+mod given_cow_fields {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item<'a> {
+        #[serde(rename = "@source", borrow)]
+        source: Cow<'a, str>,
+        #[serde(borrow)]
+        name: Cow<'a, str>,
+    }
+
+    #[rstest]
+    #[case::verbatim(
+        r#"<item source="world.rs"><name>hello</name></item>"#,
+        "world.rs",
+        "hello",
+        Some(true)
+    )]
+    #[case::entities(
+        r#"<item source="a&amp;b"><name>a &lt; b</name></item>"#,
+        "a&b",
+        "a < b",
+        Some(false)
+    )]
+    // Decoded text may be borrowed from elsewhere in the markup when the same characters appear
+    // there, as `s` and `n` do in the names, which is still the same string
+    #[case::character_references(
+        r#"<item source="&#115;"><name>&#110;</name></item>"#,
+        "s",
+        "n",
+        None
+    )]
+    #[test_log::test]
+    fn when_deserialize(
+        #[case] text: &str,
+        #[case] source: &str,
+        #[case] name: &str,
+        #[case] borrowed: Option<bool>,
+    ) {
+        let item = from_slice::<Item>(text.as_bytes()).unwrap();
+        assert_eq!(item.source, source);
+        assert_eq!(item.name, name);
+        if let Some(borrowed) = borrowed {
+            assert_eq!(matches!(item.source, Cow::Borrowed(_)), borrowed);
+            assert_eq!(matches!(item.name, Cow::Borrowed(_)), borrowed);
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_from_str_then_owned() {
+        let text = r#"<item source="world.rs"><name>hello</name></item>"#;
+        let item = from_str::<Item>(text).unwrap();
+        assert_eq!(item.name, "hello");
+        assert!(matches!(item.name, Cow::Owned(_)));
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_from_reader_then_owned() {
+        let text = r#"<item source="world.rs"><name>hello</name></item>"#;
+        let item = from_reader::<Item, _>(text.as_bytes()).unwrap();
+        assert_eq!(item.name, "hello");
+        assert!(matches!(item.name, Cow::Owned(_)));
+    }
+}
+
+mod given_borrowed_document_root {
+    use super::*;
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize() {
+        assert_eq!(from_slice::<&str>(b"<name>hello</name>").unwrap(), "hello");
+    }
+}
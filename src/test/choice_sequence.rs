@@ -54,7 +54,7 @@ mod given_child_element_choice_sequence {
     #[rstest]
     #[test_log::test]
     fn when_deserialize(text: &str, value: Document) {
-        assert_eq!(from_str::<Document>(&text).unwrap(), value);
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
     }
 
     #[rstest]
@@ -111,7 +111,7 @@ mod given_child_element_choice_sequence_with_other_elements {
     #[rstest]
     #[test_log::test]
     fn when_deserialize(text: &str, value: Document) {
-        assert_eq!(from_str::<Document>(&text).unwrap(), value);
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
     }
 
     #[rstest]
@@ -0,0 +1,74 @@
+use crate::{from_str, Deserializer, Position, SerdeXml};
+use rstest::rstest;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "order")]
+struct Order {
+    id: u32,
+}
+
+#[rstest]
+#[case::nothing("<order><id>1</id></order>")]
+#[case::whitespace("<order><id>1</id></order>\n  \n")]
+#[case::comment("<order><id>1</id></order><!-- end -->")]
+#[case::processing_instruction("<order><id>1</id></order><?log done?>")]
+#[test_log::test]
+fn given_nothing_significant_after_root_when_verify_end_then_ok(#[case] text: &str) {
+    assert_eq!(
+        SerdeXml::new()
+            .verify_end(true)
+            .from_str::<Order>(text)
+            .unwrap(),
+        Order { id: 1 }
+    );
+}
+
+#[rstest]
+#[case::verify(SerdeXml::new().verify_end(true))]
+#[case::strict(SerdeXml::new().strict(true))]
+#[test_log::test]
+fn given_second_root_when_verify_end_then_err_at_second_root(#[case] config: SerdeXml) {
+    let error = config
+        .from_str::<Order>("<order><id>1</id></order>\n<order><id>2</id></order>")
+        .unwrap_err();
+    assert_eq!(error.position(), Some(Position { line: 2, column: 1 }));
+}
+
+#[rstest]
+#[test_log::test]
+fn given_second_root_when_strict_but_end_not_verified_then_ok() {
+    assert!(SerdeXml::new()
+        .strict(true)
+        .verify_end(false)
+        .from_str::<Order>("<order><id>1</id></order><order><id>2</id></order>")
+        .is_ok());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_second_root_by_default_then_ok() {
+    assert_eq!(
+        from_str::<Order>("<order><id>1</id></order><order><id>2</id></order>").unwrap(),
+        Order { id: 1 }
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_second_root_when_end_then_err() {
+    let mut deserializer = Deserializer::from_slice(b"<order><id>1</id></order><order />");
+    assert_eq!(
+        Order::deserialize(&mut deserializer).unwrap(),
+        Order { id: 1 }
+    );
+    assert!(deserializer.end().is_err());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_single_root_when_end_then_ok() {
+    let mut deserializer = Deserializer::from_slice(b"<order><id>1</id></order><!-- end -->");
+    Order::deserialize(&mut deserializer).unwrap();
+    assert!(deserializer.end().is_ok());
+}
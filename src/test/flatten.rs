@@ -0,0 +1,272 @@
+use crate::{from_str, to_string, SerdeXml};
+use rstest::{fixture, rstest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+mod given_struct_with_flattened_attributes {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        header: Header,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Header {
+        #[serde(flatten)]
+        common: Common,
+        title: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Common {
+        #[serde(rename = "@id")]
+        id: String,
+        #[serde(rename = "@version")]
+        version: String,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><document><header id="abc" version="1"><title>Title</title></header></document>"#
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            header: Header {
+                common: Common {
+                    id: "abc".to_string(),
+                    version: "1".to_string(),
+                },
+                title: "Title".to_string(),
+            },
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(text: &str, value: Document) {
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(text: &str, value: Document) {
+        assert_eq!(to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_struct_with_flattened_elements {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        #[serde(rename = "@id")]
+        id: String,
+        #[serde(flatten)]
+        header: Header,
+        body: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Header {
+        author: String,
+        title: String,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><document id="123"><author>Someone</author><title>Title</title><body>Body</body></document>"#
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            id: "123".to_string(),
+            header: Header {
+                author: "Someone".to_string(),
+                title: "Title".to_string(),
+            },
+            body: "Body".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(text: &str, value: Document) {
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_newtype(text: &str, value: Document) {
+        #[derive(Serialize)]
+        #[serde(rename = "document")]
+        struct Wrapper<'a>(&'a Document);
+
+        assert_eq!(to_string(&Wrapper(&value)).unwrap(), text);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_then_err_naming_root_element_name(value: Document) {
+        let error = to_string(&value).unwrap_err();
+        assert!(error.to_string().contains("root_element_name"), "{error}");
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_with_root_element_name(text: &str, value: Document) {
+        let config = SerdeXml::new().root_element_name("document");
+        assert_eq!(config.clone().to_string(&value).unwrap(), text);
+        assert_eq!(config.from_str::<Document>(text).unwrap(), value);
+    }
+}
+
+mod given_struct_with_flattened_map {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        item: Item,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        #[serde(rename = "@id")]
+        id: String,
+        #[serde(flatten)]
+        others: BTreeMap<String, String>,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><document><item id="1" lang="en"><a>abc</a><b>def</b></item></document>"#
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            item: Item {
+                id: "1".to_string(),
+                others: vec![("@lang", "en"), ("a", "abc"), ("b", "def")]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            },
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(text: &str, value: Document) {
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(text: &str, value: Document) {
+        assert_eq!(to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_flattened_attributes_after_elements {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        item: Item,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        name: String,
+        #[serde(flatten)]
+        others: BTreeMap<String, String>,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_then_error() {
+        let value = Document {
+            item: Item {
+                name: "abc".to_string(),
+                others: vec![("@id".to_string(), "1".to_string())]
+                    .into_iter()
+                    .collect(),
+            },
+        };
+        assert!(to_string(&value).is_err());
+    }
+}
+
+mod given_flattened_number {
+    use super::*;
+    use serde::Deserializer;
+    use std::str::FromStr;
+
+    fn parse<'de, D: Deserializer<'de>, T: FromStr>(deserializer: D) -> Result<T, D::Error>
+    where
+        T::Err: std::fmt::Display,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        #[serde(flatten)]
+        common: Common,
+        title: String,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Common {
+        #[serde(rename = "@version")]
+        version: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "document")]
+    struct ParsedDocument {
+        #[serde(flatten)]
+        common: ParsedCommon,
+        title: String,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ParsedCommon {
+        #[serde(rename = "@version", deserialize_with = "parse")]
+        version: u32,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<document version="2"><title>Title</title></document>"#
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_err(text: &str) {
+        assert!(from_str::<Document>(text).is_err());
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_with_parse(text: &str) {
+        assert_eq!(
+            from_str::<ParsedDocument>(text).unwrap(),
+            ParsedDocument {
+                common: ParsedCommon { version: 2 },
+                title: "Title".to_string(),
+            }
+        );
+    }
+}
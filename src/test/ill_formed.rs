@@ -15,12 +15,14 @@ struct Document {
 #[case::ill_formed_element(r#"<\u{0}:/"#)]
 #[test_log::test]
 fn given_ill_formed_document_when_deserialize_then_ko(#[case] text: &str) {
-    assert!(matches!(from_str::<Document>(text), Err(Error::Reader(_))));
+    let error = from_str::<Document>(text).unwrap_err();
+    assert!(matches!(error.inner(), Error::Reader(_)));
 }
 
 #[rstest]
 #[case::attributes_instead_of_elements(r#"<document a="a" b="b" c="c" />"#)]
 #[test_log::test]
 fn given_mismatching_document_when_deserialize_then_ko(#[case] text: &str) {
-    assert!(matches!(from_str::<Document>(text), Err(Error::Custom(_))));
+    let error = from_str::<Document>(text).unwrap_err();
+    assert!(matches!(error.inner(), Error::Custom(_)));
 }
@@ -0,0 +1,198 @@
+use crate::{config::Dialect, SerdeXml};
+use rstest::{fixture, rstest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[fixture]
+fn config() -> SerdeXml {
+    SerdeXml::new().dialect(Dialect::Legacy)
+}
+
+mod given_struct_written_for_0_7 {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Item {
+        id: u32,
+        name: String,
+        #[serde(rename = "$value")]
+        text: String,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_attributes_and_text_match(config: SerdeXml) {
+        let text = r#"<item id="1" name="a">abc</item>"#;
+        assert_eq!(
+            config.from_str::<Item>(text).unwrap(),
+            Item {
+                id: 1,
+                name: "a".to_string(),
+                text: "abc".to_string(),
+            }
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_unprefixed_fields_still_match_elements(config: SerdeXml) {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Item {
+            id: u32,
+            name: String,
+        }
+
+        let text = r#"<item id="1"><name>a</name></item>"#;
+        assert_eq!(
+            config.from_str::<Item>(text).unwrap(),
+            Item {
+                id: 1,
+                name: "a".to_string(),
+            }
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_with_current_dialect_then_err() {
+        let text = r#"<item id="1" name="a">abc</item>"#;
+        assert!(SerdeXml::new().from_str::<Item>(text).is_err());
+    }
+}
+
+mod given_value_content {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "list")]
+    struct List {
+        #[serde(rename = "@name")]
+        name: String,
+        #[serde(rename = "$value")]
+        items: Vec<Item>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Item {
+        Number(u32),
+        Word(String),
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><list name="mixed"><number>1</number><word>two</word></list>"#
+    }
+
+    #[fixture]
+    fn value() -> List {
+        List {
+            name: "mixed".to_string(),
+            items: vec![Item::Number(1), Item::Word("two".to_string())],
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(config: SerdeXml, text: &str, value: List) {
+        assert_eq!(config.from_str::<List>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(config: SerdeXml, text: &str, value: List) {
+        assert_eq!(config.to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_structs_in_both_conventions {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Document {
+        old: Old,
+        new: New,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Old {
+        id: u32,
+        #[serde(rename = "$value")]
+        text: String,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct New {
+        #[serde(rename = "@id")]
+        id: u32,
+        #[serde(rename = "#text")]
+        text: String,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_both_match(config: SerdeXml) {
+        let text = r#"<document><old id="1">a</old><new id="2">b</new></document>"#;
+        assert_eq!(
+            config.from_str::<Document>(text).unwrap(),
+            Document {
+                old: Old {
+                    id: 1,
+                    text: "a".to_string()
+                },
+                new: New {
+                    id: 2,
+                    text: "b".to_string()
+                },
+            }
+        );
+    }
+}
+
+mod given_unprefixed_fields {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "order")]
+    struct Order {
+        id: u32,
+        customer: Customer,
+        line: Vec<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Customer {
+        name: String,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_round_trip_then_child_elements(config: SerdeXml) {
+        let value = Order {
+            id: 1,
+            customer: Customer {
+                name: "ACME".to_string(),
+            },
+            line: vec![2, 3],
+        };
+        let text = config.clone().to_string(&value).unwrap();
+        assert!(
+            text.ends_with("<order><id>1</id><customer><name>ACME</name></customer><line>2</line><line>3</line></order>"),
+            "{text}"
+        );
+        assert_eq!(config.from_str::<Order>(&text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_round_trip_map_then_child_elements(config: SerdeXml) {
+        let value = BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+        let config = config.root_element_name("root");
+        let text = config.clone().to_string(&value).unwrap();
+        assert!(text.ends_with("<root><a>1</a><b>2</b></root>"), "{text}");
+        assert_eq!(
+            config.from_str::<BTreeMap<String, u32>>(&text).unwrap(),
+            value
+        );
+    }
+}
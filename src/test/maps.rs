@@ -175,7 +175,7 @@ mod given_struct_with_map_of_struct_values {
     #[rstest]
     #[test_log::test]
     fn when_deserialize(text: &str, value: Document) {
-        assert_eq!(from_str::<Document>(&text).unwrap(), value);
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
     }
 
     #[rstest]
@@ -223,7 +223,7 @@ mod given_struct_with_map_of_enum_values {
     #[rstest]
     #[test_log::test]
     fn when_deserialize(text: &str, value: Document) {
-        assert_eq!(from_str::<Document>(&text).unwrap(), value);
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
     }
 
     #[rstest]
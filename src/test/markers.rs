@@ -0,0 +1,169 @@
+use crate::SerdeXml;
+use rstest::{fixture, rstest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[fixture]
+fn config() -> SerdeXml {
+    SerdeXml::new()
+        .attribute_marker("_")
+        .text_field("$text")
+        .content_field("$content")
+}
+
+mod given_struct_with_attributes_and_text {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "price")]
+    struct Price {
+        #[serde(rename = "_currency")]
+        currency: String,
+        #[serde(rename = "$text")]
+        amount: u32,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><price currency="EUR">12</price>"#
+    }
+
+    #[fixture]
+    fn value() -> Price {
+        Price {
+            currency: "EUR".to_string(),
+            amount: 12,
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(config: SerdeXml, text: &str, value: Price) {
+        assert_eq!(config.from_str::<Price>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(config: SerdeXml, text: &str, value: Price) {
+        assert_eq!(config.to_string(&value).unwrap(), text);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_with_default_markers_then_err(text: &str) {
+        assert!(SerdeXml::new().from_str::<Price>(text).is_err());
+    }
+}
+
+mod given_struct_with_content {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "list")]
+    struct List {
+        #[serde(rename = "_name")]
+        name: String,
+        #[serde(rename = "$content")]
+        items: Vec<Item>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Item {
+        Number(u32),
+        Word(String),
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><list name="mixed"><number>1</number><word>two</word><number>3</number></list>"#
+    }
+
+    #[fixture]
+    fn value() -> List {
+        List {
+            name: "mixed".to_string(),
+            items: vec![
+                Item::Number(1),
+                Item::Word("two".to_string()),
+                Item::Number(3),
+            ],
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(config: SerdeXml, text: &str, value: List) {
+        assert_eq!(config.from_str::<List>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(config: SerdeXml, text: &str, value: List) {
+        assert_eq!(config.to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_map {
+    use super::*;
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_keys_use_markers(config: SerdeXml) {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Document {
+            entry: BTreeMap<String, String>,
+        }
+
+        let text = r#"<document><entry key="a">b</entry></document>"#;
+        let document = config.from_str::<Document>(text).unwrap();
+        assert_eq!(
+            document.entry.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("$text".to_string(), "b".to_string()),
+                ("_key".to_string(), "a".to_string()),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_then_keys_use_markers(config: SerdeXml) {
+        #[derive(Serialize)]
+        #[serde(rename = "document")]
+        struct Document {
+            entry: BTreeMap<&'static str, &'static str>,
+        }
+
+        let value = Document {
+            entry: BTreeMap::from([("_key", "a"), ("child", "b")]),
+        };
+        let text = config.to_string(&value).unwrap();
+        assert!(text.ends_with(r#"<document><entry key="a"><child>b</child></entry></document>"#));
+    }
+}
+
+mod given_empty_attribute_marker {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        id: u32,
+        #[serde(rename = "#text")]
+        text: String,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_round_trip_then_fields_are_elements() {
+        let config = SerdeXml::new().attribute_marker("");
+        let item = Item {
+            id: 1,
+            text: "t".to_string(),
+        };
+        let text = config.clone().to_string(&item).unwrap();
+        assert!(text.ends_with("<item><id>1</id>t</item>"), "{text}");
+        assert_eq!(config.from_str::<Item>(&text).unwrap(), item);
+    }
+}
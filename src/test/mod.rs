@@ -1,13 +1,29 @@
+mod any;
+#[cfg(feature = "async")]
+mod asynchronous;
 mod attributes;
+mod binary;
+mod booleans;
+mod borrowed;
 mod choice_sequence;
 mod choices;
 mod doctype;
+mod end;
+mod flatten;
 mod ignore;
 mod ill_formed;
+mod legacy;
 mod maps;
+mod markers;
+mod name_matching;
 mod namespaces;
+mod nil;
 mod optional;
+mod order;
 mod overlapping;
+mod positions;
+mod report;
+mod root;
 mod sequence_container;
 mod sequences;
 mod simple_datatypes;
@@ -16,3 +32,5 @@ mod structs;
 mod text;
 mod tuples;
 mod unit_struct;
+mod unknown;
+mod untagged;
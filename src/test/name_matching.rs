@@ -0,0 +1,78 @@
+use crate::{config::NameMatching, SerdeXml};
+use rstest::rstest;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "product")]
+struct Product {
+    #[serde(rename = "@sku")]
+    sku: String,
+    #[serde(rename = "productId")]
+    product_id: u32,
+    #[serde(rename = "tag")]
+    tags: Vec<String>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Kind {
+    SpareParts,
+    Service,
+}
+
+fn product() -> Product {
+    Product {
+        sku: "A1".to_string(),
+        product_id: 1,
+        tags: vec!["new".to_string(), "sale".to_string()],
+        kind: Kind::SpareParts,
+    }
+}
+
+const EXACT: &str = r#"<product sku="A1"><productId>1</productId><tag>new</tag><tag>sale</tag><kind><spareParts /></kind></product>"#;
+const OTHER_CASE: &str = r#"<Product SKU="A1"><ProductID>1</ProductID><Tag>new</Tag><TAG>sale</TAG><Kind><SpareParts /></Kind></Product>"#;
+const PREFIXED: &str = r#"<p:product xmlns:p="urn:example:products" p:sku="A1"><p:productId>1</p:productId><p:tag>new</p:tag><tag>sale</tag><p:kind><p:spareParts /></p:kind></p:product>"#;
+
+#[rstest]
+#[case::exact(NameMatching::Exact, EXACT)]
+#[case::ignore_ascii_case_exact(NameMatching::IgnoreAsciiCase, EXACT)]
+#[case::ignore_ascii_case_other_case(NameMatching::IgnoreAsciiCase, OTHER_CASE)]
+#[case::local_name_exact(NameMatching::LocalName, EXACT)]
+#[case::local_name_prefixed(NameMatching::LocalName, PREFIXED)]
+#[test_log::test]
+fn given_names_when_matching_policy_then_ok(#[case] policy: NameMatching, #[case] text: &str) {
+    let config = SerdeXml::new().name_matching(policy).verify_root_name(true);
+    assert_eq!(config.from_str::<Product>(text).unwrap(), product());
+}
+
+#[rstest]
+#[case::exact_other_case(NameMatching::Exact, OTHER_CASE)]
+#[case::exact_prefixed(NameMatching::Exact, PREFIXED)]
+#[case::ignore_ascii_case_prefixed(NameMatching::IgnoreAsciiCase, PREFIXED)]
+#[case::local_name_other_case(NameMatching::LocalName, OTHER_CASE)]
+#[test_log::test]
+fn given_names_when_matching_policy_then_err(#[case] policy: NameMatching, #[case] text: &str) {
+    let config = SerdeXml::new().name_matching(policy).verify_root_name(true);
+    assert!(config.from_str::<Product>(text).is_err());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_prefixed_field_when_local_name_then_any_namespace_matches() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Item {
+        #[serde(rename = "{urn:example:a}id")]
+        id: u32,
+    }
+
+    let text = r#"<item xmlns:b="urn:example:b"><b:id>1</b:id></item>"#;
+    assert!(SerdeXml::new().from_str::<Item>(text).is_err());
+    assert_eq!(
+        SerdeXml::new()
+            .name_matching(NameMatching::LocalName)
+            .from_str::<Item>(text)
+            .unwrap(),
+        Item { id: 1 }
+    );
+}
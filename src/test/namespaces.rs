@@ -204,3 +204,263 @@ mod given_enum_ns {
         assert_eq!(config.to_string(&value).unwrap(), text);
     }
 }
+
+mod given_producers_with_different_prefixes {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "d:document")]
+    struct Document {
+        #[serde(rename = "d:content")]
+        content: String,
+        #[serde(rename = "@d:id")]
+        id: u32,
+        #[serde(rename = "d:item", default)]
+        items: Vec<u32>,
+    }
+
+    #[fixture]
+    fn config() -> SerdeXml {
+        SerdeXml::new().namespace("d", "urn:example:document")
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            content: "abc".to_string(),
+            id: 1,
+            items: vec![2, 3],
+        }
+    }
+
+    #[rstest]
+    #[case::same_prefix(
+        r#"<d:document xmlns:d="urn:example:document" d:id="1"><d:content>abc</d:content><d:item>2</d:item><d:item>3</d:item></d:document>"#
+    )]
+    #[case::other_prefix(
+        r#"<doc:document xmlns:doc="urn:example:document" doc:id="1"><doc:content>abc</doc:content><doc:item>2</doc:item><doc:item>3</doc:item></doc:document>"#
+    )]
+    #[case::default_namespace(
+        r#"<document xmlns="urn:example:document" xmlns:x="urn:example:document" x:id="1"><content>abc</content><item>2</item><item>3</item></document>"#
+    )]
+    #[case::mixed_prefixes(
+        r#"<a:document xmlns:a="urn:example:document" xmlns:b="urn:example:document" b:id="1"><b:content>abc</b:content><a:item>2</a:item><b:item>3</b:item></a:document>"#
+    )]
+    #[test_log::test]
+    fn when_deserialize_with_configured_prefix_then_ok(
+        config: SerdeXml,
+        value: Document,
+        #[case] text: &str,
+    ) {
+        assert_eq!(config.from_str::<Document>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_with_other_namespace_then_err(config: SerdeXml) {
+        let text = r#"<d:document xmlns:d="urn:example:other" d:id="1"><d:content>abc</d:content></d:document>"#;
+        assert!(config.from_str::<Document>(text).is_err());
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_without_configured_prefix_then_prefix_matches_literally() {
+        let text = r#"<doc:document xmlns:doc="urn:example:document" doc:id="1"><doc:content>abc</doc:content></doc:document>"#;
+        assert!(SerdeXml::new().from_str::<Document>(text).is_err());
+        let text = r#"<d:document xmlns:d="urn:example:other" d:id="1"><d:content>abc</d:content></d:document>"#;
+        assert!(SerdeXml::new().from_str::<Document>(text).is_ok());
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_map_then_keys_use_configured_prefix() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Document {
+            #[serde(rename = "doc:content")]
+            content: BTreeMap<String, String>,
+        }
+
+        let text = r#"<doc xmlns:doc="urn:example:document" xmlns:x="urn:example:x"><doc:content><doc:a>1</doc:a><b>2</b><x:c>3</x:c></doc:content></doc>"#;
+        let document = SerdeXml::new()
+            .namespace("doc", "urn:example:document")
+            .from_str::<Document>(text)
+            .unwrap();
+        assert_eq!(
+            document.content.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("b".to_string(), "2".to_string()),
+                ("doc:a".to_string(), "1".to_string()),
+                ("x:c".to_string(), "3".to_string()),
+            ]
+        );
+    }
+}
+
+mod given_clark_notation {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Document {
+        #[serde(rename = "{urn:example:document}content")]
+        content: String,
+        #[serde(rename = "@{urn:example:document}id")]
+        id: u32,
+        #[serde(rename = "{urn:example:document}kind")]
+        kind: Kind,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Kind {
+        #[serde(rename = "{urn:example:document}report")]
+        Report,
+        #[serde(rename = "{urn:example:document}letter")]
+        Letter,
+    }
+
+    #[rstest]
+    #[case::prefixed(
+        r#"<document xmlns:d="urn:example:document" d:id="1"><d:content>abc</d:content><d:kind><d:letter/></d:kind></document>"#
+    )]
+    #[case::default_namespace(
+        r#"<document xmlns:d="urn:example:document" d:id="1"><content xmlns="urn:example:document">abc</content><kind xmlns="urn:example:document"><letter/></kind></document>"#
+    )]
+    #[test_log::test]
+    fn when_deserialize_then_matched_by_uri(#[case] text: &str) {
+        assert_eq!(
+            SerdeXml::new().from_str::<Document>(text).unwrap(),
+            Document {
+                content: "abc".to_string(),
+                id: 1,
+                kind: Kind::Letter,
+            }
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_unqualified_element_then_err() {
+        let text = r#"<document xmlns:d="urn:example:document" d:id="1"><content>abc</content><d:kind><d:letter/></d:kind></document>"#;
+        assert!(SerdeXml::new().from_str::<Document>(text).is_err());
+    }
+}
+
+mod given_clark_notation_when_serialize {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "{urn:example:document}document")]
+    struct Document {
+        #[serde(rename = "@{urn:example:document}id")]
+        id: u32,
+        #[serde(rename = "{urn:example:document}content")]
+        content: String,
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            id: 1,
+            content: "abc".to_string(),
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn with_configured_prefix_then_prefix_is_written(value: Document) {
+        let config = SerdeXml::new().namespace("d", "urn:example:document");
+        let text = config.clone().to_string(&value).unwrap();
+        assert!(text.ends_with(
+            r#"<d:document xmlns:d="urn:example:document" d:id="1"><d:content>abc</d:content></d:document>"#
+        ));
+        assert_eq!(config.from_str::<Document>(&text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn without_configured_prefix_then_err(value: Document) {
+        assert!(SerdeXml::new().to_string(&value).is_err());
+    }
+}
+
+mod given_nested_elements_when_serialize {
+    use super::*;
+    use crate::Error;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "d:document")]
+    struct Document {
+        #[serde(rename = "d:section")]
+        sections: Vec<Section>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Section {
+        #[serde(rename = "@xlink:href")]
+        href: String,
+        #[serde(rename = "d:title")]
+        title: String,
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            sections: vec![
+                Section {
+                    href: "#a".to_string(),
+                    title: "A".to_string(),
+                },
+                Section {
+                    href: "#b".to_string(),
+                    title: "B".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn then_namespaces_are_declared_on_root_only(value: Document) {
+        let text = SerdeXml::new()
+            .emitter(EmitterConfig::new().perform_indent(true))
+            .default_namespace("urn:example:default")
+            .namespace("d", "urn:example:document")
+            .namespace("xlink", "http://www.w3.org/1999/xlink")
+            .to_string(&value)
+            .unwrap();
+        assert_eq!(
+            text,
+            indoc!(
+                r##"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <d:document xmlns="urn:example:default" xmlns:d="urn:example:document" xmlns:xlink="http://www.w3.org/1999/xlink">
+                  <d:section xlink:href="#a">
+                    <d:title>A</d:title>
+                  </d:section>
+                  <d:section xlink:href="#b">
+                    <d:title>B</d:title>
+                  </d:section>
+                </d:document>"##
+            )
+        );
+    }
+
+    #[rstest]
+    #[case::element("xlink", "d:document")]
+    #[case::attribute("d", "xlink:href")]
+    #[test_log::test]
+    fn with_unbound_prefix_then_err(
+        value: Document,
+        #[case] configured: &str,
+        #[case] unbound_name: &str,
+    ) {
+        let error = SerdeXml::new()
+            .namespace(configured, "urn:example:configured")
+            .to_string(&value)
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::UnboundPrefix { name, .. } if name == unbound_name),
+            "{error}"
+        );
+    }
+}
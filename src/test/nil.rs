@@ -0,0 +1,147 @@
+use crate::{from_str, SerdeXml};
+use rstest::rstest;
+use serde::{Deserialize, Serialize};
+
+const XSI: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "person")]
+struct Person {
+    name: String,
+    age: Option<u32>,
+    email: Option<String>,
+}
+
+#[rstest]
+#[case::self_closing(
+    r#"<person xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><name>Ada</name><age xsi:nil="true"/><email xsi:nil="true"/></person>"#
+)]
+#[case::declared_on_element(
+    r#"<person><name>Ada</name><age xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="1"/></person>"#
+)]
+#[case::other_prefix(
+    r#"<person xmlns:i="http://www.w3.org/2001/XMLSchema-instance"><name>Ada</name><age i:nil="true"></age><email i:nil="true">ignored</email></person>"#
+)]
+#[test_log::test]
+fn given_nil_elements_when_deserialize_then_none(#[case] text: &str) {
+    assert_eq!(
+        from_str::<Person>(text).unwrap(),
+        Person {
+            name: "Ada".to_string(),
+            age: None,
+            email: None,
+        }
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_nil_false_when_deserialize_then_some() {
+    let text = r#"<person xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><name>Ada</name><age xsi:nil="false">36</age></person>"#;
+    assert_eq!(
+        from_str::<Person>(text).unwrap(),
+        Person {
+            name: "Ada".to_string(),
+            age: Some(36),
+            email: None,
+        }
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_nil_in_other_namespace_when_deserialize_then_err() {
+    let text = r#"<person xmlns:xsi="urn:not-xsi"><name>Ada</name><age xsi:nil="true"/></person>"#;
+    assert!(from_str::<Person>(text).is_err());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_nil_items_in_sequence_when_deserialize_then_none_items() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Readings {
+        value: Vec<Option<u32>>,
+    }
+
+    let text = r#"<readings xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><value>1</value><value xsi:nil="true"/><value>3</value></readings>"#;
+    assert_eq!(
+        from_str::<Readings>(text).unwrap(),
+        Readings {
+            value: vec![Some(1), None, Some(3)]
+        }
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_nil_root_when_deserialize_then_none() {
+    let text = r#"<person xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true"/>"#;
+    assert_eq!(from_str::<Option<Person>>(text).unwrap(), None);
+}
+
+mod given_none_fields {
+    use super::*;
+
+    fn person() -> Person {
+        Person {
+            name: "Ada".to_string(),
+            age: None,
+            email: Some("ada@example.com".to_string()),
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_by_default_then_left_out() {
+        let text = SerdeXml::new().to_string(&person()).unwrap();
+        assert!(text.ends_with("<person><name>Ada</name><email>ada@example.com</email></person>"));
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_as_nil_then_nil_element() {
+        let text = SerdeXml::new()
+            .none_as_nil(true)
+            .to_string(&person())
+            .unwrap();
+        assert!(text.ends_with(&format!(
+            r#"<person xmlns:xsi="{XSI}"><name>Ada</name><age xsi:nil="true" /><email>ada@example.com</email></person>"#
+        )), "{text}");
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_several_as_nil_then_namespace_declared_once() {
+        let text = SerdeXml::new()
+            .none_as_nil(true)
+            .to_string(&Person {
+                email: None,
+                ..person()
+            })
+            .unwrap();
+        assert!(text.ends_with(&format!(
+            r#"<person xmlns:xsi="{XSI}"><name>Ada</name><age xsi:nil="true" /><email xsi:nil="true" /></person>"#
+        )), "{text}");
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_as_nil_with_declared_namespace_then_declared_once() {
+        let text = SerdeXml::new()
+            .none_as_nil(true)
+            .namespace("xsi", XSI)
+            .to_string(&person())
+            .unwrap();
+        assert!(text.ends_with(&format!(
+            r#"<person xmlns:xsi="{XSI}"><name>Ada</name><age xsi:nil="true" /><email>ada@example.com</email></person>"#
+        )), "{text}");
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_round_trip_as_nil_then_same_value() {
+        let config = SerdeXml::new().none_as_nil(true);
+        let text = config.clone().to_string(&person()).unwrap();
+        assert_eq!(config.from_str::<Person>(&text).unwrap(), person());
+    }
+}
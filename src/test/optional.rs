@@ -194,7 +194,7 @@ mod given_nested_struct_with_optional_attribute {
             content: Content { a: content_value },
         };
 
-        assert_eq!(from_str::<Document<T>>(&text).unwrap(), value);
+        assert_eq!(from_str::<Document<T>>(text).unwrap(), value);
     }
 }
 
@@ -0,0 +1,124 @@
+use crate::{Error, SerdeXml};
+use rstest::{fixture, rstest};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "order")]
+struct Order {
+    #[serde(rename = "@id")]
+    id: u32,
+    customer: String,
+    #[serde(default)]
+    note: Option<String>,
+    #[serde(default, rename = "line")]
+    lines: Vec<String>,
+    total: u32,
+}
+
+#[fixture]
+fn config() -> SerdeXml {
+    SerdeXml::new().verify_order(true)
+}
+
+fn order() -> Order {
+    Order {
+        id: 1,
+        customer: "ACME".to_string(),
+        note: None,
+        lines: vec!["a".to_string(), "b".to_string()],
+        total: 2,
+    }
+}
+
+#[rstest]
+#[case::all_fields(
+    r#"<order id="1"><customer>ACME</customer><note>urgent</note><line>a</line><line>b</line><total>2</total></order>"#,
+    Order { note: Some("urgent".to_string()), ..order() }
+)]
+#[case::missing_optional_field(
+    r#"<order id="1"><customer>ACME</customer><line>a</line><line>b</line><total>2</total></order>"#,
+    order()
+)]
+#[case::unknown_element(
+    r#"<order id="1"><customer>ACME</customer><gift /><line>a</line><line>b</line><total>2</total></order>"#,
+    order()
+)]
+#[test_log::test]
+fn given_elements_in_order_when_verify_order_then_ok(
+    config: SerdeXml,
+    #[case] text: &str,
+    #[case] expected: Order,
+) {
+    assert_eq!(config.from_str::<Order>(text).unwrap(), expected);
+}
+
+#[rstest]
+#[case::swapped_fields(
+    r#"<order id="1"><line>a</line><line>b</line><customer>ACME</customer><total>2</total></order>"#,
+    "/order/customer",
+    "Element 'customer' is out of order, it must come before 'line'"
+)]
+#[case::field_after_last(
+    r#"<order id="1"><customer>ACME</customer><total>2</total><note>urgent</note></order>"#,
+    "/order/note",
+    "Element 'note' is out of order, it must come before 'total'"
+)]
+#[case::repeated_field_apart(
+    r#"<order id="1"><customer>ACME</customer><line>a</line><total>2</total><line>b</line></order>"#,
+    "/order/line",
+    "Element 'line' is out of order, it must come before 'total'"
+)]
+#[test_log::test]
+fn given_elements_out_of_order_when_verify_order_then_err(
+    config: SerdeXml,
+    #[case] text: &str,
+    #[case] path: &str,
+    #[case] message: &str,
+) {
+    let error = config.from_str::<Order>(text).unwrap_err();
+    assert!(
+        matches!(error.inner(), Error::UnexpectedOrder { .. }),
+        "{error}"
+    );
+    assert_eq!(error.path().unwrap().to_string(), path);
+    assert_eq!(error.inner().to_string(), message);
+}
+
+#[rstest]
+#[test_log::test]
+fn given_repeated_field_apart_when_overlapping_sequences_and_verify_order_then_err(
+    config: SerdeXml,
+) {
+    let text = r#"<order id="1"><customer>ACME</customer><line>a</line><total>2</total><line>b</line></order>"#;
+    assert_eq!(
+        SerdeXml::new()
+            .overlapping_sequences(true)
+            .from_str::<Order>(text)
+            .unwrap(),
+        order()
+    );
+    let error = config
+        .overlapping_sequences(true)
+        .from_str::<Order>(text)
+        .unwrap_err();
+    assert!(
+        matches!(error.inner(), Error::UnexpectedOrder { .. }),
+        "{error}"
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_elements_out_of_order_when_strict_then_err() {
+    let text = r#"<order id="1"><total>2</total><customer>ACME</customer></order>"#;
+    assert!(SerdeXml::new().from_str::<Order>(text).is_ok());
+    assert!(SerdeXml::new()
+        .strict(true)
+        .from_str::<Order>(text)
+        .is_err());
+    assert!(SerdeXml::new()
+        .strict(true)
+        .verify_order(false)
+        .from_str::<Order>(text)
+        .is_ok());
+}
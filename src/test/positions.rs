@@ -0,0 +1,159 @@
+use crate::{config::NameMatching, from_str, Error, Position, SerdeXml, Step};
+use rstest::rstest;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename = "project")]
+#[allow(dead_code)]
+struct Project {
+    name: String,
+    #[serde(default)]
+    dependency: Vec<Dependency>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Dependency {
+    #[serde(rename = "@version")]
+    version: u32,
+    #[serde(default)]
+    scope: Option<Scope>,
+    artifact: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Scope {
+    Compile,
+    Test,
+}
+
+fn position(line: u64, column: u64) -> Option<Position> {
+    Some(Position { line, column })
+}
+
+#[rstest]
+#[case::missing_field_in_root("<?xml version=\"1.0\"?>\n<project>\n</project>", position(2, 1))]
+#[case::missing_field_in_child(
+    "<project>\n  <name>abc</name>\n  <dependency version=\"1\" />\n</project>",
+    position(3, 3)
+)]
+#[case::invalid_attribute(
+    "<project>\n  <name>abc</name>\n  <dependency version=\"x\"><artifact>a</artifact></dependency>\n</project>",
+    position(3, 3)
+)]
+#[case::invalid_text(
+    "<project>\n  <name>abc</name>\n  <dependency version=\"1\">\n    <artifact>a</artifact>\n    <scope>runtime</scope>\n  </dependency>\n</project>",
+    position(5, 12)
+)]
+#[case::ill_formed("<project>\n  <name>abc</nom>\n</project>", position(2, 17))]
+#[test_log::test]
+fn when_deserialize_then_error_has_position(
+    #[case] text: &str,
+    #[case] expected: Option<Position>,
+) {
+    let error = from_str::<Project>(text).unwrap_err();
+    assert_eq!(error.position(), expected, "{error}");
+}
+
+#[rstest]
+#[test_log::test]
+fn when_display_then_path_and_position_are_shown() {
+    let error = from_str::<Project>("<?xml version=\"1.0\"?><project></project>").unwrap_err();
+    assert!(matches!(error.inner(), Error::Custom(_)));
+    assert_eq!(
+        error.to_string(),
+        "Custom: missing field `name` (at /project, line 1, column 22)"
+    );
+}
+
+#[rstest]
+#[case::root("<project></project>", "/project")]
+#[case::attribute_in_sequence(
+    r#"<project><name>abc</name><dependency version="1"><artifact>a</artifact></dependency><dependency version="2"><artifact>b</artifact></dependency><dependency version="x"><artifact>c</artifact></dependency></project>"#,
+    "/project/dependency[3]/@version"
+)]
+#[case::text_in_sequence(
+    r#"<project><name>abc</name><dependency version="1"><artifact>a</artifact><scope>runtime</scope></dependency></project>"#,
+    "/project/dependency[1]/scope/text()"
+)]
+#[case::element_in_sequence(
+    r#"<project><name>abc</name><dependency version="1"></dependency></project>"#,
+    "/project/dependency[1]"
+)]
+#[test_log::test]
+fn when_deserialize_then_error_has_path(#[case] text: &str, #[case] expected: &str) {
+    let error = from_str::<Project>(text).unwrap_err();
+    assert_eq!(error.path().unwrap().to_string(), expected, "{error}");
+}
+
+#[rstest]
+#[test_log::test]
+fn when_deserialize_then_path_has_steps() {
+    let text = r#"<project><name>abc</name><dependency version="x"><artifact>a</artifact></dependency></project>"#;
+    let error = from_str::<Project>(text).unwrap_err();
+    assert_eq!(
+        error.path().unwrap().steps(),
+        &[
+            Step::Element {
+                name: "project".to_string(),
+                index: None
+            },
+            Step::Element {
+                name: "dependency".to_string(),
+                index: Some(1)
+            },
+            Step::Attribute("version".to_string()),
+        ]
+    );
+    assert!(matches!(error.inner(), Error::ParseInt(_)));
+}
+
+#[rstest]
+#[test_log::test]
+fn given_nested_elements_with_same_name_when_deserialize_then_path_has_both() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Doc {
+        a: Inner,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Inner {
+        a: u32,
+    }
+
+    let error = from_str::<Doc>("<doc><a><a>x</a></a></doc>").unwrap_err();
+    assert_eq!(error.path().unwrap().to_string(), "/doc/a/a", "{error}");
+}
+
+#[rstest]
+#[case::ignore_ascii_case(
+    NameMatching::IgnoreAsciiCase,
+    "<doc><A>1</A><A>x</A></doc>",
+    "/doc/A[2]"
+)]
+#[case::local_name(
+    NameMatching::LocalName,
+    r#"<doc xmlns:q="urn:example"><q:a>1</q:a><q:a>x</q:a></doc>"#,
+    "/doc/q:a[2]"
+)]
+#[test_log::test]
+fn given_sequence_when_name_matching_then_path_has_document_names(
+    #[case] policy: NameMatching,
+    #[case] text: &str,
+    #[case] expected: &str,
+) {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Doc {
+        a: Vec<u32>,
+    }
+
+    let error = SerdeXml::new()
+        .name_matching(policy)
+        .from_str::<Doc>(text)
+        .unwrap_err();
+    assert_eq!(error.path().unwrap().to_string(), expected, "{error}");
+}
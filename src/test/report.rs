@@ -0,0 +1,139 @@
+use super::unknown::{
+    given_custom_map_visitor::Settings, given_struct_with_flattened_fields::Document, Order, Status,
+};
+use crate::{IgnoredKind, Position, SerdeXml};
+use rstest::rstest;
+
+#[rstest]
+#[case::child_element(
+    r#"<order id="1"><customer><name>ACME</name></customer><note>urgent</note></order>"#,
+    IgnoredKind::Element,
+    "/order/note"
+)]
+#[case::attribute(
+    r#"<order id="1" priority="high"><customer><name>ACME</name></customer></order>"#,
+    IgnoredKind::Attribute,
+    "/order/@priority"
+)]
+#[case::text(
+    r#"<order id="1">urgent<customer><name>ACME</name></customer></order>"#,
+    IgnoredKind::Text,
+    "/order/text()"
+)]
+#[case::nested_element(
+    r#"<order id="1"><customer><name>ACME</name><vat>123</vat></customer></order>"#,
+    IgnoredKind::Element,
+    "/order/customer/vat"
+)]
+#[case::nested_element_with_same_name(
+    r#"<order id="1"><customer><name>ACME</name><customer /></customer></order>"#,
+    IgnoredKind::Element,
+    "/order/customer/customer"
+)]
+#[case::attribute_of_primitive(
+    r#"<order id="1"><customer><name lang="en">ACME</name></customer></order>"#,
+    IgnoredKind::Attribute,
+    "/order/customer/name/@lang"
+)]
+#[case::struct_variant(
+    r#"<order id="1"><customer><name>ACME</name></customer><status><closed reason="paid" by="me" /></status></order>"#,
+    IgnoredKind::Attribute,
+    "/order/status/closed/@by"
+)]
+#[case::unit_variant(
+    r#"<order id="1"><customer><name>ACME</name></customer><status><open since="today" /></status></order>"#,
+    IgnoredKind::Attribute,
+    "/order/status/open/@since"
+)]
+#[case::sequence_item(
+    r#"<order id="1"><customer><name>ACME</name></customer><line sku="a" /><line sku="b"><qty>2</qty></line></order>"#,
+    IgnoredKind::Element,
+    "/order/line[2]/qty"
+)]
+#[test_log::test]
+fn given_unknown_content_when_report_then_ignored_with_path(
+    #[case] text: &str,
+    #[case] kind: IgnoredKind,
+    #[case] path: &str,
+) {
+    let (_, ignored) = SerdeXml::new().from_str_with_report::<Order>(text).unwrap();
+    assert_eq!(ignored.len(), 1, "{ignored:?}");
+    assert_eq!(ignored[0].kind, kind);
+    assert_eq!(ignored[0].path.to_string(), path);
+}
+
+#[rstest]
+#[test_log::test]
+fn given_unknown_content_when_report_then_ignored_in_document_order_with_positions() {
+    let text = r#"<order id="1" priority="high">
+  <customer>
+    <name>ACME</name>
+    <vat>123</vat>
+  </customer>
+  <note>urgent</note>
+</order>"#;
+    let (order, ignored) = SerdeXml::new()
+        .from_reader_with_report::<Order, _>(text.as_bytes())
+        .unwrap();
+    assert_eq!(order.customer.name, "ACME");
+    let kinds_and_paths = ignored
+        .iter()
+        .map(|ignored| (ignored.kind, ignored.path.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds_and_paths,
+        vec![
+            (IgnoredKind::Attribute, "/order/@priority".to_string()),
+            (IgnoredKind::Element, "/order/customer/vat".to_string()),
+            (IgnoredKind::Element, "/order/note".to_string()),
+        ]
+    );
+    // Attributes are located by their element
+    assert_eq!(ignored[0].position.map(|position| position.line), Some(1));
+    assert_eq!(ignored[1].position, Some(Position { line: 4, column: 5 }));
+    assert_eq!(ignored[2].position, Some(Position { line: 6, column: 3 }));
+}
+
+#[rstest]
+#[test_log::test]
+fn given_known_content_when_report_then_empty() {
+    let text = r#"<order xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:example order.xsd" id="1"><customer><name>ACME</name></customer><status><closed reason="paid" /></status><line sku="a" /></order>"#;
+    let (order, ignored) = SerdeXml::new().from_str_with_report::<Order>(text).unwrap();
+    assert_eq!(
+        order.status,
+        Some(Status::Closed {
+            reason: "paid".to_string()
+        })
+    );
+    assert_eq!(ignored, vec![]);
+}
+
+#[rstest]
+#[test_log::test]
+fn given_struct_with_flattened_fields_when_report_then_buffered_content_unchecked() {
+    let text = r#"<doc id="1" bogus="x"><name>n</name><junk /></doc>"#;
+    let (_, ignored) = SerdeXml::new()
+        .from_str_with_report::<Document>(text)
+        .unwrap();
+    assert_eq!(
+        ignored
+            .iter()
+            .map(|ignored| (ignored.kind, ignored.path.to_string()))
+            .collect::<Vec<_>>(),
+        vec![
+            (IgnoredKind::Unchecked, "/doc/@bogus".to_string()),
+            (IgnoredKind::Unchecked, "/doc/name".to_string()),
+            (IgnoredKind::Unchecked, "/doc/junk".to_string()),
+        ]
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_custom_map_visitor_when_report_then_empty() {
+    let text = r#"<settings mode="fast"><a>1</a><b>2</b></settings>"#;
+    let (_, ignored) = SerdeXml::new()
+        .from_str_with_report::<Settings>(text)
+        .unwrap();
+    assert_eq!(ignored, vec![]);
+}
@@ -0,0 +1,360 @@
+use crate::{error::Result, to_string, SerdeXml};
+use rstest::{fixture, rstest};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+#[fixture]
+fn config() -> SerdeXml {
+    SerdeXml::new().root_element_name("root")
+}
+
+fn round_trip<T>(config: SerdeXml, value: T, expected: &str)
+where
+    T: std::fmt::Debug + PartialEq + Serialize + for<'de> Deserialize<'de>,
+{
+    let text = config.clone().to_string(&value).unwrap();
+    assert_eq!(text, format!("{DECLARATION}{expected}"));
+    assert_eq!(config.from_str::<T>(&text).unwrap(), value);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+    #[serde(rename = "@x")]
+    x: i32,
+    #[serde(rename = "@y")]
+    y: i32,
+}
+
+#[rstest]
+#[test_log::test]
+fn given_bool_when_round_trip_then_root_element(config: SerdeXml) {
+    round_trip(config, true, "<root>true</root>");
+}
+
+#[rstest]
+#[test_log::test]
+fn given_integer_when_round_trip_then_root_element(config: SerdeXml) {
+    round_trip(config, -42i64, "<root>-42</root>");
+}
+
+#[rstest]
+#[test_log::test]
+fn given_string_when_round_trip_then_root_element(config: SerdeXml) {
+    round_trip(config, "abc".to_string(), "<root>abc</root>");
+}
+
+#[rstest]
+#[test_log::test]
+fn given_unit_when_round_trip_then_empty_root_element(config: SerdeXml) {
+    round_trip(config, (), "<root />");
+}
+
+#[rstest]
+#[test_log::test]
+fn given_sequence_when_round_trip_then_item_elements(config: SerdeXml) {
+    round_trip(
+        config,
+        vec![1, 2],
+        "<root><item>1</item><item>2</item></root>",
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_empty_sequence_when_round_trip_then_empty_root_element(config: SerdeXml) {
+    round_trip(config, Vec::<i32>::new(), "<root />");
+}
+
+#[rstest]
+#[test_log::test]
+fn given_sequence_of_structs_when_round_trip_then_item_elements(config: SerdeXml) {
+    round_trip(
+        config,
+        vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }],
+        r#"<root><item x="1" y="2" /><item x="3" y="4" /></root>"#,
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_tuple_when_round_trip_then_root_element(config: SerdeXml) {
+    round_trip(config, (1, -2, 3), "<root>1 -2 3</root>");
+}
+
+#[rstest]
+#[test_log::test]
+fn given_map_when_round_trip_then_child_elements(config: SerdeXml) {
+    round_trip(
+        config,
+        BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+        "<root><a>1</a><b>2</b></root>",
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_hash_map_when_deserialize_then_child_elements(config: SerdeXml) {
+    assert_eq!(
+        config
+            .from_str::<HashMap<String, String>>("<root><a>x</a><b>y</b></root>")
+            .unwrap(),
+        HashMap::from([
+            ("a".to_string(), "x".to_string()),
+            ("b".to_string(), "y".to_string()),
+        ])
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_struct_when_serialize_then_struct_name_is_kept(config: SerdeXml) {
+    assert_eq!(
+        config.to_string(&Point { x: 1, y: 2 }).unwrap(),
+        format!(r#"{DECLARATION}<Point x="1" y="2" />"#)
+    );
+}
+
+#[rstest]
+#[case::bool(to_string(&true))]
+#[case::integer(to_string(&1))]
+#[case::string(to_string(&"abc"))]
+#[case::unit(to_string(&()))]
+#[case::sequence(to_string(&vec![1, 2]))]
+#[case::map(to_string(&BTreeMap::from([("a", 1)])))]
+#[test_log::test]
+fn given_no_root_element_name_when_serialize_then_err(#[case] result: Result<String>) {
+    assert!(result.is_err());
+}
+
+mod given_runtime_root_element_name {
+    use super::*;
+    use crate::{Error, Serializer};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "message")]
+    struct Message {
+        id: u32,
+    }
+
+    #[rstest]
+    #[case::order("order")]
+    #[case::cancel("cancel")]
+    #[test_log::test]
+    fn when_round_trip_then_runtime_name(#[case] root: &str) {
+        let value = Message { id: 1 };
+        let text = SerdeXml::new().to_string_with_root(root, &value).unwrap();
+        assert_eq!(text, format!("{DECLARATION}<{root}><id>1</id></{root}>"));
+        assert_eq!(
+            SerdeXml::new()
+                .from_str_with_root::<Message>(root, &text)
+                .unwrap(),
+            value
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_round_trip_sequence_then_runtime_name() {
+        let text = SerdeXml::new()
+            .to_string_with_root("msg", &vec![1, 2])
+            .unwrap();
+        assert_eq!(
+            text,
+            format!("{DECLARATION}<msg><item>1</item><item>2</item></msg>")
+        );
+        assert_eq!(
+            SerdeXml::new()
+                .from_str_with_root::<Vec<u32>>("msg", &text)
+                .unwrap(),
+            vec![1, 2]
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_with_serializer_then_runtime_name() {
+        let mut serializer =
+            Serializer::new_from_writer(Vec::new()).with_root_element_name("cancel");
+        Message { id: 1 }.serialize(&mut serializer).unwrap();
+        let text = String::from_utf8(serializer.into_inner()).unwrap();
+        assert_eq!(text, format!("{DECLARATION}<cancel><id>1</id></cancel>"));
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_primitive_then_runtime_name() {
+        assert_eq!(
+            SerdeXml::new().to_string_with_root("count", &3).unwrap(),
+            format!("{DECLARATION}<count>3</count>")
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize_with_configured_namespace_then_prefixed() {
+        let text = SerdeXml::new()
+            .namespace("m", "urn:example:messages")
+            .to_string_with_root("{urn:example:messages}cancel", &Message { id: 1 })
+            .unwrap();
+        assert_eq!(
+            text,
+            format!(
+                r#"{DECLARATION}<m:cancel xmlns:m="urn:example:messages"><id>1</id></m:cancel>"#
+            )
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_other_root_then_err() {
+        let error = SerdeXml::new()
+            .from_str_with_root::<Message>("cancel", "<order><id>1</id></order>")
+            .unwrap_err();
+        assert!(matches!(
+            error.inner(),
+            Error::UnexpectedRoot { expected, found } if expected == "cancel" && found == "order"
+        ));
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_without_runtime_name_then_any_root() {
+        assert_eq!(
+            SerdeXml::new()
+                .from_str::<Message>("<cancel><id>1</id></cancel>")
+                .unwrap(),
+            Message { id: 1 }
+        );
+    }
+}
+
+mod given_root_name_verification {
+    use super::*;
+    use crate::Error;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "order")]
+    struct Order {
+        id: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "order")]
+    struct OrderId(u32);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "order")]
+    struct OrderPair(u32, u32);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "order")]
+    enum OrderState {
+        #[serde(rename = "open")]
+        Open,
+    }
+
+    fn assert_unexpected_root<T: std::fmt::Debug>(result: Result<T>) {
+        let error = result.unwrap_err();
+        assert!(
+            matches!(
+                error.inner(),
+                Error::UnexpectedRoot { expected, found } if expected == "order" && found == "invoice"
+            ),
+            "{error}"
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_disabled_then_any_root() {
+        assert_eq!(
+            SerdeXml::new()
+                .from_str::<Order>("<invoice><id>1</id></invoice>")
+                .unwrap(),
+            Order { id: 1 }
+        );
+    }
+
+    #[rstest]
+    #[case::verify(SerdeXml::new().verify_root_name(true))]
+    #[case::strict(SerdeXml::new().strict(true))]
+    #[test_log::test]
+    fn when_enabled_then_same_root_ok(#[case] config: SerdeXml) {
+        assert_eq!(
+            config
+                .from_str::<Order>("<order><id>1</id></order>")
+                .unwrap(),
+            Order { id: 1 }
+        );
+    }
+
+    #[rstest]
+    #[case::verify(SerdeXml::new().verify_root_name(true))]
+    #[case::strict(SerdeXml::new().strict(true))]
+    #[test_log::test]
+    fn when_enabled_then_other_root_err(#[case] config: SerdeXml) {
+        assert_unexpected_root(config.from_str::<Order>("<invoice><id>1</id></invoice>"));
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_strict_but_disabled_then_any_root() {
+        assert!(SerdeXml::new()
+            .strict(true)
+            .verify_root_name(false)
+            .from_str::<Order>("<invoice><id>1</id></invoice>")
+            .is_ok());
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_enabled_for_other_types_then_other_root_err() {
+        let config = SerdeXml::new().verify_root_name(true);
+        assert_unexpected_root(config.clone().from_str::<OrderId>("<invoice>1</invoice>"));
+        assert_unexpected_root(
+            config
+                .clone()
+                .from_str::<OrderPair>("<invoice>1 2</invoice>"),
+        );
+        assert_unexpected_root(config.from_str::<OrderState>("<invoice><open /></invoice>"));
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_enabled_for_unnamed_type_then_configured_root_name() {
+        let config = SerdeXml::new()
+            .verify_root_name(true)
+            .root_element_name("order");
+        assert_eq!(
+            config
+                .clone()
+                .from_str::<Vec<u32>>("<order><item>1</item></order>")
+                .unwrap(),
+            vec![1]
+        );
+        assert_unexpected_root(config.from_str::<Vec<u32>>("<invoice><item>1</item></invoice>"));
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_enabled_with_runtime_name_then_runtime_name_wins() {
+        assert!(SerdeXml::new()
+            .verify_root_name(true)
+            .from_str_with_root::<Order>("invoice", "<invoice><id>1</id></invoice>")
+            .is_ok());
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_enabled_for_path_stream_then_records_named_by_path() {
+        let text = "<orders><entry><id>1</id></entry><entry><id>2</id></entry></orders>";
+        let orders = SerdeXml::new()
+            .strict(true)
+            .iter_path_from_reader::<Order, _>("orders/entry", text.as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(orders, vec![Order { id: 1 }, Order { id: 2 }]);
+    }
+}
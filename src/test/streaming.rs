@@ -1,4 +1,4 @@
-use crate::{from_reader, to_writer};
+use crate::{from_reader, to_writer, Deserializer, SerdeXml};
 use rstest::rstest;
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
@@ -26,6 +26,90 @@ fn given_input_with_multiple_root_elements_when_deserializing_muliple_times_then
     from_reader::<Event, _>(&mut r).unwrap();
 }
 
+#[rstest]
+#[test_log::test]
+fn given_input_with_multiple_root_elements_when_iterating_then_ok() {
+    let text = r#"
+        <event type="a" timestamp="2000-01-01T00:00Z">an event</event>
+        <event type="b" timestamp="2000-01-01T00:01Z">another event</event>
+        "#;
+
+    let events = SerdeXml::new()
+        .iter_from_reader::<Event, _>(text.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            Event {
+                r#type: "a".to_string(),
+                timestamp: "2000-01-01T00:00Z".to_string(),
+                message: "an event".to_string(),
+            },
+            Event {
+                r#type: "b".to_string(),
+                timestamp: "2000-01-01T00:01Z".to_string(),
+                message: "another event".to_string(),
+            },
+        ]
+    );
+}
+
+#[rstest]
+#[case::empty("")]
+#[case::whitespace("  \n  ")]
+#[test_log::test]
+fn given_input_without_root_element_when_iterating_then_error_and_end(#[case] text: &str) {
+    let mut iter = Deserializer::new_from_reader(text.as_bytes()).into_iter::<Event>();
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_records_when_iterating_past_the_end_then_none() {
+    let text = r#"<event type="a" timestamp="2000-01-01T00:00Z">an event</event>"#;
+    let mut iter = Deserializer::new_from_reader(text.as_bytes()).into_iter::<Event>();
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_mismatching_record_when_iterating_then_error_and_continue() {
+    let text = r#"
+        <event type="a" timestamp="2000-01-01T00:00Z">an event</event>
+        <event type="b"><nested>missing timestamp</nested></event>
+        <event type="c" timestamp="2000-01-01T00:02Z">a third event</event>
+        "#;
+
+    let results = Deserializer::new_from_reader(text.as_bytes())
+        .into_iter::<Event>()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap().r#type, "c");
+}
+
+#[rstest]
+#[test_log::test]
+fn given_ill_formed_record_when_iterating_then_error_and_end() {
+    let text = r#"
+        <event type="a" timestamp="2000-01-01T00:00Z">an event</event>
+        <event type="b" timestamp="2000-01-01T00:01Z">another event</evnt>
+        <event type="c" timestamp="2000-01-01T00:02Z">a third event</event>
+        "#;
+
+    let results = Deserializer::from_slice(text.as_bytes())
+        .into_iter::<Event>()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+}
+
 #[rstest]
 #[test_log::test]
 fn given_multiple_values_when_serializing_multiple_times_then_ok() {
@@ -43,3 +127,103 @@ fn given_multiple_values_when_serializing_multiple_times_then_ok() {
     to_writer(&mut output, &event1).unwrap();
     to_writer(&mut output, &event2).unwrap();
 }
+
+mod given_items_selected_by_path {
+    use super::*;
+    use std::io::Read;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Item {
+        title: String,
+    }
+
+    /// An RSS feed that never ends
+    struct EndlessFeed {
+        buffer: Cursor<Vec<u8>>,
+        count: usize,
+    }
+
+    impl EndlessFeed {
+        fn new() -> Self {
+            Self {
+                buffer: Cursor::new(
+                    b"<rss><channel><title>Feed</title><link>https://example.com</link>".to_vec(),
+                ),
+                count: 0,
+            }
+        }
+    }
+
+    impl Read for EndlessFeed {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.buffer.position() as usize == self.buffer.get_ref().len() {
+                self.count += 1;
+                self.buffer = Cursor::new(
+                    format!(
+                        "<item><title>{}</title><guid>{}</guid></item>",
+                        self.count, self.count
+                    )
+                    .into_bytes(),
+                );
+            }
+            self.buffer.read(buf)
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_iterating_endless_input_then_items_are_read_as_they_come() {
+        let items = SerdeXml::new()
+            .iter_path_from_reader::<Item, _>("rss/channel/item", EndlessFeed::new())
+            .skip(9_999)
+            .take(2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            items,
+            vec![
+                Item {
+                    title: "10000".to_string()
+                },
+                Item {
+                    title: "10001".to_string()
+                }
+            ]
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_iterating_then_only_matching_elements_are_deserialized() {
+        let text = r#"
+            <rss>
+                <item><title>not in a channel</title></item>
+                <channel>
+                    <title>Feed</title>
+                    <item><title>a</title></item>
+                    <image><item><title>nested too deep</title></item></image>
+                    <item><title>b</title></item>
+                </channel>
+            </rss>
+            "#;
+        let items = Deserializer::from_slice(text.as_bytes())
+            .into_iter_path::<Item>("/rss/channel/item")
+            .map(|item| item.map(|item| item.title))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(items, vec!["a", "b"]);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_item_does_not_match_then_error_has_path_and_iteration_continues() {
+        let text = r#"<rss><channel><item><title>a</title></item><item /><item><title>c</title></item></channel></rss>"#;
+        let results = Deserializer::new_from_reader(text.as_bytes())
+            .into_iter_path::<Item>("rss/channel/item")
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        let error = results[1].as_ref().unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "/rss/channel/item");
+        assert_eq!(results[2].as_ref().unwrap().title, "c");
+    }
+}
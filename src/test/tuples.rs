@@ -130,3 +130,233 @@ mod given_struct_with_tuple_struct_field {
         assert_eq!(to_string(&value).unwrap(), text);
     }
 }
+
+mod given_tuple_struct_of_structs {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "line")]
+    struct Line(Point, Point);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        #[serde(rename = "@x")]
+        x: i32,
+        #[serde(rename = "@y")]
+        y: i32,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><line><Point x="1" y="2" /><Point x="3" y="-4" /></line>"#
+    }
+
+    #[fixture]
+    fn value() -> Line {
+        Line(Point { x: 1, y: 2 }, Point { x: 3, y: -4 })
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(text: &str, value: Line) {
+        assert_eq!(from_str::<Line>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(text: &str, value: Line) {
+        assert_eq!(to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_struct_with_tuple_of_struct_and_sequence {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        order: (Header, Vec<Item>),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Header {
+        customer: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        #[serde(rename = "@sku")]
+        sku: String,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><document><order><Header><customer>ACME</customer></Header><Item sku="a1" /><Item sku="b2" /></order></document>"#
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            order: (
+                Header {
+                    customer: "ACME".to_string(),
+                },
+                vec![
+                    Item {
+                        sku: "a1".to_string(),
+                    },
+                    Item {
+                        sku: "b2".to_string(),
+                    },
+                ],
+            ),
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(text: &str, value: Document) {
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(text: &str, value: Document) {
+        assert_eq!(to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_struct_with_tuple_of_enums {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        shapes: (Shape, Shape),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Shape {
+        Circle {
+            #[serde(rename = "@r")]
+            r: u32,
+        },
+        Square {
+            #[serde(rename = "@side")]
+            side: u32,
+        },
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><document><shapes><square side="2" /><circle r="1" /></shapes></document>"#
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            shapes: (Shape::Square { side: 2 }, Shape::Circle { r: 1 }),
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(text: &str, value: Document) {
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(text: &str, value: Document) {
+        assert_eq!(to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_struct_with_mixed_tuple {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        entry: (u32, i32, Point, String),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        #[serde(rename = "@x")]
+        x: i32,
+        #[serde(rename = "@y")]
+        y: i32,
+    }
+
+    #[fixture]
+    fn text() -> &'static str {
+        r#"<?xml version="1.0" encoding="UTF-8"?><document><entry>3 -1<Point x="1" y="2" />origin</entry></document>"#
+    }
+
+    #[fixture]
+    fn value() -> Document {
+        Document {
+            entry: (3, -1, Point { x: 1, y: 2 }, "origin".to_string()),
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize(text: &str, value: Document) {
+        assert_eq!(from_str::<Document>(text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_serialize(text: &str, value: Document) {
+        assert_eq!(to_string(&value).unwrap(), text);
+    }
+}
+
+mod given_mixed_tuple_with_text_that_cannot_be_read_back {
+    use super::*;
+    use crate::Error;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        #[serde(rename = "@x")]
+        x: i32,
+        #[serde(rename = "@y")]
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "document")]
+    struct Document {
+        entry: (u32, Point, String),
+        label: (String, Point),
+    }
+
+    fn document(entry: &str, label: &str) -> Document {
+        Document {
+            entry: (1, Point { x: 0, y: 0 }, entry.to_string()),
+            label: (label.to_string(), Point { x: 1, y: 1 }),
+        }
+    }
+
+    #[rstest]
+    #[case::whitespace_after_element(document("s p", "a"))]
+    #[case::whitespace_before_element(document("s", "a b"))]
+    #[case::empty_after_element(document("", "a"))]
+    #[case::empty_before_element(document("s", ""))]
+    #[test_log::test]
+    fn when_serialize_then_err(#[case] value: Document) {
+        let error = to_string(&value).unwrap_err();
+        assert!(matches!(error, Error::Unsupported(_)), "{error}");
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_round_trip_without_whitespace_then_same_value() {
+        let value = document("s", "a");
+        let text = to_string(&value).unwrap();
+        assert_eq!(from_str::<Document>(&text).unwrap(), value);
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{from_str, Error, SerdeXml};
+use rstest::{fixture, rstest};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Model shared with the tests of reports, which skip what these tests deny
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "order")]
+pub(super) struct Order {
+    #[serde(rename = "@id")]
+    pub(super) id: u32,
+    pub(super) customer: Customer,
+    #[serde(default)]
+    pub(super) status: Option<Status>,
+    #[serde(default)]
+    pub(super) extra: BTreeMap<String, String>,
+    #[serde(default, rename = "line")]
+    pub(super) lines: Vec<Line>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub(super) struct Customer {
+    pub(super) name: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub(super) struct Line {
+    #[serde(rename = "@sku")]
+    pub(super) sku: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Status {
+    Open,
+    Closed {
+        #[serde(rename = "@reason")]
+        reason: String,
+    },
+}
+
+#[fixture]
+fn config() -> SerdeXml {
+    SerdeXml::new().deny_unknown(true)
+}
+
+pub(super) fn order() -> Order {
+    Order {
+        id: 1,
+        customer: Customer {
+            name: "ACME".to_string(),
+        },
+        status: None,
+        extra: BTreeMap::new(),
+        lines: vec![],
+    }
+}
+
+#[rstest]
+#[case::child_element(
+    r#"<order id="1"><customer><name>ACME</name></customer><note>urgent</note></order>"#,
+    "/order/note"
+)]
+#[case::attribute(
+    r#"<order id="1" priority="high"><customer><name>ACME</name></customer></order>"#,
+    "/order/@priority"
+)]
+#[case::text(
+    r#"<order id="1">urgent<customer><name>ACME</name></customer></order>"#,
+    "/order/text()"
+)]
+#[case::nested_element(
+    r#"<order id="1"><customer><name>ACME</name><vat>123</vat></customer></order>"#,
+    "/order/customer/vat"
+)]
+#[case::attribute_of_primitive(
+    r#"<order id="1"><customer><name lang="en">ACME</name></customer></order>"#,
+    "/order/customer/name/@lang"
+)]
+#[case::struct_variant(
+    r#"<order id="1"><customer><name>ACME</name></customer><status><closed reason="paid" by="me" /></status></order>"#,
+    "/order/status/closed/@by"
+)]
+#[case::unit_variant(
+    r#"<order id="1"><customer><name>ACME</name></customer><status><open since="today" /></status></order>"#,
+    "/order/status/open/@since"
+)]
+#[case::map_value(
+    r#"<order id="1"><customer><name>ACME</name></customer><extra><a unit="kg">1</a></extra></order>"#,
+    "/order/extra/a/@unit"
+)]
+#[test_log::test]
+fn given_unknown_content_when_deny_unknown_then_err_with_path(
+    config: SerdeXml,
+    #[case] text: &str,
+    #[case] path: &str,
+) {
+    let error = config.from_str::<Order>(text).unwrap_err();
+    assert!(matches!(error.inner(), Error::UnknownContent(_)), "{error}");
+    assert_eq!(error.path().unwrap().to_string(), path);
+}
+
+#[rstest]
+#[test_log::test]
+fn given_unknown_content_by_default_then_skipped() {
+    let text = r#"<order id="1" priority="high">urgent<customer><name lang="en">ACME</name><vat>123</vat></customer><note>urgent</note></order>"#;
+    assert_eq!(from_str::<Order>(text).unwrap(), order());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_unknown_content_when_strict_then_err() {
+    let text = r#"<order id="1"><customer><name>ACME</name></customer><note>urgent</note></order>"#;
+    assert!(SerdeXml::new()
+        .strict(true)
+        .from_str::<Order>(text)
+        .is_err());
+    assert!(SerdeXml::new()
+        .strict(true)
+        .deny_unknown(false)
+        .from_str::<Order>(text)
+        .is_ok());
+}
+
+#[rstest]
+#[test_log::test]
+fn given_known_content_when_deny_unknown_then_ok(config: SerdeXml) {
+    let text = r#"<order id="1"><customer><name>ACME</name></customer><status><closed reason="paid" /></status><extra><a>1</a><b>2</b></extra></order>"#;
+    assert_eq!(
+        config.from_str::<Order>(text).unwrap(),
+        Order {
+            status: Some(Status::Closed {
+                reason: "paid".to_string()
+            }),
+            extra: BTreeMap::from([
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "2".to_string())
+            ]),
+            ..order()
+        }
+    );
+}
+
+#[rstest]
+#[test_log::test]
+fn given_xsi_attributes_when_deny_unknown_then_ok(config: SerdeXml) {
+    let text = r#"<order xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:example order.xsd" id="1"><customer><name xsi:type="xs:string">ACME</name></customer></order>"#;
+    assert_eq!(config.from_str::<Order>(text).unwrap(), order());
+}
+
+pub(super) mod given_struct_with_flattened_fields {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "doc")]
+    pub(in crate::test) struct Document {
+        #[serde(rename = "@id")]
+        id: u32,
+        #[serde(flatten)]
+        body: Body,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Body {
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "doc", deny_unknown_fields)]
+    struct StrictDocument {
+        #[serde(rename = "@id")]
+        id: u32,
+        #[serde(flatten)]
+        body: Body,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Documents {
+        doc: StrictDocument,
+    }
+
+    fn document() -> Document {
+        Document {
+            id: 1,
+            body: Body {
+                name: "n".to_string(),
+            },
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deny_unknown_then_ok(config: SerdeXml) {
+        let text = r#"<doc id="1"><name>n</name></doc>"#;
+        assert_eq!(config.from_str::<Document>(text).unwrap(), document());
+    }
+
+    #[rstest]
+    #[case::attribute(r#"<doc id="1" bogus="x"><name>n</name></doc>"#)]
+    #[case::element(r#"<doc id="1"><name>n</name><junk /></doc>"#)]
+    #[test_log::test]
+    fn when_deny_unknown_fields_then_err(#[case] text: &str) {
+        let error = from_str::<StrictDocument>(text).unwrap_err();
+        assert!(matches!(error.inner(), Error::Custom(_)), "{error}");
+        assert_eq!(error.path().unwrap().to_string(), "/doc");
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_nested_and_deny_unknown_fields_then_err_with_path() {
+        let text = r#"<documents><doc id="1"><name>n</name><junk /></doc></documents>"#;
+        let error = from_str::<Documents>(text).unwrap_err();
+        assert!(matches!(error.inner(), Error::Custom(_)), "{error}");
+        assert_eq!(error.path().unwrap().to_string(), "/documents/doc");
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_not_deny_unknown_then_unknown_content_skipped() {
+        let text = r#"<doc id="1" bogus="x"><name>n</name><junk /></doc>"#;
+        assert_eq!(from_str::<Document>(text).unwrap(), document());
+    }
+}
+
+pub(super) mod given_custom_map_visitor {
+    use super::*;
+    use serde::de::{MapAccess, Visitor};
+    use std::fmt;
+
+    /// Reads a map of strings, describing itself as a struct
+    #[derive(Debug, PartialEq)]
+    pub(in crate::test) struct Settings(BTreeMap<String, String>);
+
+    impl<'de> Deserialize<'de> for Settings {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct SettingsVisitor;
+
+            impl<'de> Visitor<'de> for SettingsVisitor {
+                type Value = Settings;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("struct Settings")
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Settings, A::Error> {
+                    let mut entries = BTreeMap::new();
+                    while let Some((key, value)) = map.next_entry()? {
+                        entries.insert(key, value);
+                    }
+                    Ok(Settings(entries))
+                }
+            }
+
+            deserializer.deserialize_map(SettingsVisitor)
+        }
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deny_unknown_then_ok(config: SerdeXml) {
+        let text = r#"<settings mode="fast"><a>1</a><b>2</b></settings>"#;
+        assert_eq!(
+            config.from_str::<Settings>(text).unwrap(),
+            Settings(BTreeMap::from([
+                ("@mode".to_string(), "fast".to_string()),
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "2".to_string()),
+            ]))
+        );
+    }
+}
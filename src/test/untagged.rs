@@ -0,0 +1,306 @@
+use crate::{de::Replay, from_str, SerdeXml};
+use rstest::rstest;
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Detailed {
+    amount: u32,
+    currency: String,
+}
+
+#[derive(Debug, PartialEq)]
+enum Price {
+    Amount(u32),
+    Detailed(Detailed),
+    Text(String),
+}
+
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let replay = Replay::deserialize(deserializer)?;
+        replay
+            .deserialize_as()
+            .map(Price::Amount)
+            .or_else(|_| replay.deserialize_as().map(Price::Detailed))
+            .or_else(|_| replay.deserialize_as().map(Price::Text))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+mod given_replay_in_element_position {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        price: Price,
+        name: String,
+    }
+
+    #[rstest]
+    #[case::amount("<price>12</price>", Price::Amount(12))]
+    #[case::detailed("<price><amount>12</amount><currency>EUR</currency></price>", Price::Detailed(Detailed { amount: 12, currency: "EUR".to_string() }))]
+    #[case::text("<price>free</price>", Price::Text("free".to_string()))]
+    #[test_log::test]
+    fn when_deserialize(#[case] price_text: &str, #[case] price: Price) {
+        let text = format!("<item>{price_text}<name>abc</name></item>");
+        assert_eq!(
+            from_str::<Item>(&text).unwrap(),
+            Item {
+                price,
+                name: "abc".to_string()
+            }
+        );
+    }
+}
+
+mod given_replay_in_attribute_position {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        #[serde(rename = "@price")]
+        price: Price,
+    }
+
+    #[rstest]
+    #[case::amount(r#"<item price="12" />"#, Price::Amount(12))]
+    #[case::text(r#"<item price="free" />"#, Price::Text("free".to_string()))]
+    #[test_log::test]
+    fn when_deserialize(#[case] text: &str, #[case] price: Price) {
+        assert_eq!(from_str::<Item>(text).unwrap(), Item { price });
+    }
+}
+
+mod given_sequence_of_replays {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        price: Vec<Price>,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize() {
+        let text = "<item><price>1</price><price><amount>2</amount><currency>USD</currency></price><price>3</price></item>";
+        assert_eq!(
+            from_str::<Item>(text).unwrap(),
+            Item {
+                price: vec![
+                    Price::Amount(1),
+                    Price::Detailed(Detailed {
+                        amount: 2,
+                        currency: "USD".to_string()
+                    }),
+                    Price::Amount(3),
+                ]
+            }
+        );
+    }
+}
+
+mod given_replay_in_document_root {
+    use super::*;
+
+    #[rstest]
+    #[case::amount("<price>12</price>", Price::Amount(12))]
+    #[case::detailed("<price><amount>12</amount><currency>EUR</currency></price>", Price::Detailed(Detailed { amount: 12, currency: "EUR".to_string() }))]
+    #[test_log::test]
+    fn when_deserialize(#[case] text: &str, #[case] price: Price) {
+        assert_eq!(from_str::<Price>(text).unwrap(), price);
+    }
+}
+
+mod given_replay_with_configuration {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Flag {
+        Bool(bool),
+        Text(String),
+    }
+
+    impl<'de> Deserialize<'de> for Flag {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let replay = Replay::deserialize(deserializer)?;
+            replay
+                .deserialize_as()
+                .map(Flag::Bool)
+                .or_else(|_| replay.deserialize_as().map(Flag::Text))
+                .map_err(serde::de::Error::custom)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        flag: Flag,
+        #[serde(rename = "@flag")]
+        flag_attribute: Flag,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_configuration_applies() {
+        let text = r#"<item flag="no"><flag>yes</flag></item>"#;
+        assert_eq!(
+            SerdeXml::new()
+                .boolean_values(&["yes"], &["no"])
+                .from_str::<Item>(text)
+                .unwrap(),
+            Item {
+                flag: Flag::Bool(true),
+                flag_attribute: Flag::Bool(false),
+            }
+        );
+        assert_eq!(
+            from_str::<Item>(text).unwrap(),
+            Item {
+                flag: Flag::Text("yes".to_string()),
+                flag_attribute: Flag::Text("no".to_string()),
+            }
+        );
+    }
+}
+
+mod given_replay_with_namespaces_and_markers {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Measured {
+        #[serde(rename = "_p:unit")]
+        unit: String,
+        #[serde(rename = "p:amount")]
+        amount: u32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Quantity {
+        Count(u32),
+        Measured(Measured),
+    }
+
+    impl<'de> Deserialize<'de> for Quantity {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let replay = Replay::deserialize(deserializer)?;
+            replay
+                .deserialize_as()
+                .map(Quantity::Count)
+                .or_else(|_| replay.deserialize_as().map(Quantity::Measured))
+                .map_err(serde::de::Error::custom)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        quantity: Quantity,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_names_resolve_as_configured() {
+        let text = r#"<item xmlns:q="urn:p"><quantity q:unit="kg"><q:amount>3</q:amount></quantity></item>"#;
+        assert_eq!(
+            SerdeXml::new()
+                .namespace("p", "urn:p")
+                .attribute_marker("_")
+                .from_str::<Item>(text)
+                .unwrap(),
+            Item {
+                quantity: Quantity::Measured(Measured {
+                    unit: "kg".to_string(),
+                    amount: 3,
+                }),
+            }
+        );
+    }
+}
+
+mod given_serde_untagged_enum_with_non_string_variants {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Price {
+        Amount(u32),
+        Detailed { amount: u32, currency: String },
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        price: Price,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_then_unsupported() {
+        assert!(from_str::<Item>("<item><price>12</price></item>").is_err());
+    }
+}
+
+mod given_serde_untagged_enum_with_the_variants_of_a_replay {
+    use super::*;
+
+    /// The variants of [super::Price], which tries them with a [Replay] instead
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Price {
+        Amount(u32),
+        Detailed(Detailed),
+        Text(String),
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        price: Price,
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_number_then_string_variant() {
+        assert_eq!(
+            from_str::<Item>("<item><price>12</price></item>").unwrap(),
+            Item {
+                price: Price::Text("12".to_string())
+            }
+        );
+    }
+
+    #[rstest]
+    #[test_log::test]
+    fn when_deserialize_struct_with_number_then_err() {
+        let text = "<item><price><amount>12</amount><currency>EUR</currency></price></item>";
+        assert!(from_str::<Item>(text).is_err());
+    }
+}
+
+mod given_serde_untagged_enum_with_string_variants {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Price {
+        Amount(String),
+        Detailed { amount: String, currency: String },
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "item")]
+    struct Item {
+        price: Price,
+    }
+
+    #[rstest]
+    #[case::amount("<item><price>12</price></item>", Price::Amount("12".to_string()))]
+    #[case::detailed("<item><price><amount>12</amount><currency>EUR</currency></price></item>", Price::Detailed { amount: "12".to_string(), currency: "EUR".to_string() })]
+    #[test_log::test]
+    fn when_deserialize(#[case] text: &str, #[case] price: Price) {
+        assert_eq!(from_str::<Item>(text).unwrap(), Item { price });
+    }
+}